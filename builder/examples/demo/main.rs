@@ -26,12 +26,19 @@ use derive_builder::Builder;
 //     assert_eq!(command.current_dir, None);
 // }
 
+// 生成コードがプレリュードの名前に依存していないことを確認するために型を上書きする
+#[allow(dead_code)]
 type Option = ();
+#[allow(dead_code)]
 type Some = ();
+#[allow(dead_code)]
 type None = ();
+#[allow(dead_code)]
 type Result = ();
+#[allow(dead_code)]
 type Box = ();
 
+#[allow(dead_code)]
 #[derive(Builder)]
 pub struct Command {
     executable: String,
@@ -70,6 +70,8 @@ impl syn::parse::Parse for Skips {
     }
 }
 
+// 解析結果は Debug で表示するだけなので、フィールドを読まない警告を抑制する
+#[allow(dead_code)]
 #[derive(Debug)]
 struct Fields(Punctuated<Field, Token![,]>);
 
@@ -83,6 +85,7 @@ impl syn::parse::Parse for Fields {
     }
 }
 
+#[allow(dead_code)]
 #[derive(Debug)]
 struct Field {
     key: syn::Ident,
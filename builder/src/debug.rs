@@ -0,0 +1,127 @@
+use derive_support::debug::{
+    extract_debug_attributes, generate_value, generate_wrappers, FieldFormat,
+};
+use proc_macro2::{Span, TokenTree};
+use quote::quote;
+use syn::{ext::IdentExt, punctuated::Punctuated};
+
+use crate::{unwrap_ty, InnerType};
+
+/// Reject `debug(expr = "...")` referring to `self` or to another field. The Debug impl of the
+/// builder binds only the field itself, and `self` there is the builder instead of the struct.
+fn check_expr(
+    expr: &syn::Expr,
+    ident: &syn::Ident,
+    named: &Punctuated<syn::Field, syn::token::Comma>,
+) -> syn::Result<()> {
+    let is_outside = |name: &syn::Ident| {
+        name == "self"
+            || named
+                .iter()
+                .filter_map(|f| f.ident.as_ref())
+                .any(|other| other != ident && other.unraw() == name.unraw())
+    };
+    fn mentions(
+        tokens: proc_macro2::TokenStream,
+        is_outside: &dyn Fn(&syn::Ident) -> bool,
+    ) -> bool {
+        // `.len()` や `Vec::new` のように `.` や `::` に続く名前は変数ではないので除く（`..` は除く）
+        let mut after_accessor = false;
+        let mut after_dot = false;
+        tokens.into_iter().any(|token| {
+            let found = match &token {
+                TokenTree::Ident(name) => !after_accessor && is_outside(name),
+                TokenTree::Group(group) => mentions(group.stream(), is_outside),
+                _ => false,
+            };
+            let punct = match &token {
+                TokenTree::Punct(punct) => Some(punct.as_char()),
+                _ => None,
+            };
+            after_accessor = matches!(punct, Some('.' | ':')) && !after_dot;
+            after_dot = punct == Some('.');
+            found
+        })
+    }
+
+    if mentions(quote! { #expr }, &is_outside) {
+        return Err(syn::Error::new_spanned(
+            expr,
+            "`debug(expr = \"...\")` can refer only to the field itself with `builder(debug)`",
+        ));
+    }
+    Ok(())
+}
+
+/// Generate the Debug impl of `builder(debug)`, which prints the set fields the same way as
+/// CustomDebug prints the struct and the unset fields as `<unset>`.
+pub(crate) fn generate_debug_impl(
+    builder_ident: &syn::Ident,
+    named: &Punctuated<syn::Field, syn::token::Comma>,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let name = builder_ident.to_string();
+    let debug_builder = syn::Ident::new("debug_builder", Span::mixed_site());
+    let mut has_skipped = false;
+    let mut all_attrs = vec![];
+    let mut fields = vec![];
+
+    for f in named {
+        let debug_attrs = extract_debug_attributes(f)?;
+        if debug_attrs.skip {
+            has_skipped = true;
+            continue;
+        }
+        let ident = f.ident.as_ref().unwrap();
+        if let Some(FieldFormat::Expr(expr)) = &debug_attrs.format {
+            check_expr(expr, ident, named)?;
+        }
+
+        let field_name = match &debug_attrs.rename {
+            Some(rename) => rename.value(),
+            None => ident.unraw().to_string(),
+        };
+        // CustomDebug と同じく、フィールド名で値を参照できるように束縛する
+        let value = generate_value(&debug_attrs, ident);
+        // Option のフィールドはビルダーでも Option のままなので、元の型の値として書式を適用する
+        let (pattern, binding) = match unwrap_ty(&f.ty) {
+            InnerType::Option(_) => (
+                quote! { std::option::Option::Some(_) },
+                quote! { let #ident = &self.#ident; },
+            ),
+            _ => (quote! { std::option::Option::Some(ref #ident) }, quote! {}),
+        };
+
+        fields.push(quote! {
+            match self.#ident {
+                #pattern => {
+                    #binding
+                    #debug_builder.field(#field_name, #value);
+                }
+                std::option::Option::None => {
+                    #debug_builder.field(#field_name, &format_args!("<unset>"));
+                }
+            }
+        });
+        all_attrs.push(debug_attrs);
+    }
+
+    // CustomDebug と同じく、スキップしたフィールドがあれば `..` を出力する
+    let finish = if has_skipped {
+        quote! { finish_non_exhaustive }
+    } else {
+        quote! { finish }
+    };
+    let wrappers = generate_wrappers(all_attrs.iter());
+
+    Ok(quote! {
+        #[automatically_derived]
+        impl std::fmt::Debug for #builder_ident {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                #wrappers
+                let mut #debug_builder = f.debug_struct(#name);
+                #(#fields)*
+                #debug_builder.#finish()
+            }
+        }
+    })
+}
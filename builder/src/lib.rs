@@ -1,11 +1,13 @@
 use proc_macro::TokenStream;
 use quote::{format_ident, quote};
-use syn::{parse_macro_input, punctuated::Punctuated, DeriveInput, Type};
+use syn::{ext::IdentExt, parse_macro_input, punctuated::Punctuated, DeriveInput, Type};
+
+mod debug;
 
 enum InnerType {
-    OptionType(Type),
-    VecType(Type),
-    PrimitiveType,
+    Option(Type),
+    Vec(Type),
+    Primitive,
 }
 
 /// Returns InnerType enum with unwrapped Type
@@ -27,9 +29,9 @@ fn unwrap_ty(ty: &Type) -> InnerType {
                 if args.len() == 1 {
                     if let Some(syn::GenericArgument::Type(inner_ty)) = args.first() {
                         if ident == "Option" {
-                            return InnerType::OptionType(inner_ty.clone());
+                            return InnerType::Option(inner_ty.clone());
                         } else if ident == "Vec" {
-                            return InnerType::VecType(inner_ty.clone());
+                            return InnerType::Vec(inner_ty.clone());
                         }
                     }
                 }
@@ -37,7 +39,7 @@ fn unwrap_ty(ty: &Type) -> InnerType {
         }
     }
 
-    InnerType::PrimitiveType
+    InnerType::Primitive
 }
 
 enum ParseBuilderAttributeResult {
@@ -72,6 +74,52 @@ fn unwrap_builder_attr_value(attrs: &[syn::Attribute]) -> Option<ParseBuilderAtt
     })
 }
 
+/// options given by the struct level #[builder(...)] attribute
+#[derive(Default)]
+struct BuilderOptions {
+    serde: bool,
+    from_env: bool,
+    separator: Option<syn::LitStr>,
+    const_fn: bool,
+    debug: bool,
+}
+
+fn extract_builder_options(attrs: &[syn::Attribute]) -> syn::Result<BuilderOptions> {
+    let mut options = BuilderOptions::default();
+
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("builder")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("serde") {
+                options.serde = true;
+                Ok(())
+            } else if meta.path.is_ident("from_env") {
+                options.from_env = true;
+                Ok(())
+            } else if meta.path.is_ident("const") {
+                options.const_fn = true;
+                Ok(())
+            } else if meta.path.is_ident("debug") {
+                options.debug = true;
+                Ok(())
+            } else if meta.path.is_ident("separator") {
+                options.separator = Some(meta.value()?.parse()?);
+                Ok(())
+            } else {
+                Err(meta.error("unsupported builder option"))
+            }
+        })?;
+    }
+
+    if let (false, Some(separator)) = (options.from_env, &options.separator) {
+        return Err(syn::Error::new_spanned(
+            separator,
+            "`separator` can only be used together with `builder(from_env)`",
+        ));
+    }
+
+    Ok(options)
+}
+
 fn extract_named_fields(data: &syn::Data) -> &Punctuated<syn::Field, syn::token::Comma> {
     let syn::Data::Struct(syn::DataStruct { fields: syn::Fields::Named(syn::FieldsNamed{ named, .. }), .. }) = data else {
         unimplemented!("This macro can only be applied to struct");
@@ -93,6 +141,123 @@ fn generate_default_setter_with(
     }
 }
 
+// builder(const) では &mut self を返すセッターを const fn にできないので、self を受け取って返す
+// 古い値の drop は const fn では評価できないため、代入せずに取り出した値を forget する
+// 値が漏れないように、設定済みのフィールドを再び設定すると panic させ、forget するのは常に None にする
+fn generate_const_setter_with(
+    ident: &Option<syn::Ident>,
+    ty: &syn::Type,
+) -> proc_macro2::TokenStream {
+    quote! {
+        const fn #ident(mut self, #ident: #ty) -> Self {
+            if self.#ident.is_some() {
+                panic!(concat!(stringify!(#ident), " is already set"));
+            }
+            std::mem::forget(std::mem::replace(
+                &mut self.#ident,
+                std::option::Option::Some(#ident),
+            ));
+            self
+        }
+    }
+}
+
+/// generate setters and a panicking build() which can be evaluated at compile time
+fn generate_const_builder_impl(
+    original_ident: &syn::Ident,
+    named: &Punctuated<syn::Field, syn::token::Comma>,
+) -> syn::Result<proc_macro2::TokenStream> {
+    // Vec の要素を追加する push は const fn では呼べないため、each のセッターを生成できない
+    if let Some(f) = named
+        .iter()
+        .find(|f| matches!(unwrap_ty(&f.ty), InnerType::Vec(_)))
+    {
+        return Err(syn::Error::new_spanned(
+            &f.ty,
+            "`Vec` fields are not supported by `builder(const)`, consider using `&'static [T]`",
+        ));
+    }
+
+    let setters = named.iter().map(|f| {
+        let ident = &f.ident;
+        let ty = &f.ty;
+
+        match unwrap_ty(ty) {
+            InnerType::Option(inner_ty) => generate_const_setter_with(ident, &inner_ty),
+            _ => generate_const_setter_with(ident, ty),
+        }
+    });
+
+    let idents = named.iter().map(|f| &f.ident).collect::<Vec<_>>();
+
+    let build_fields = named.iter().map(|f| {
+        let ident = &f.ident;
+
+        match unwrap_ty(&f.ty) {
+            InnerType::Option(_) => quote! {
+                #ident
+            },
+            // match で条件付きに移動すると drop が残るため、移動だけで済む expect を使う
+            _ => quote! {
+                #ident: #ident.expect(concat!(stringify!(#ident), " is not set"))
+            },
+        }
+    });
+
+    Ok(quote! {
+        #(#setters)*
+
+        /// Panics when a required field is not set, which becomes a compile
+        /// error when evaluated in a const context.
+        const fn build(self) -> #original_ident {
+            // デストラクタを持つフィールドでも const fn で評価できるように、各フィールドを
+            // 一度だけ読み出してから self を forget する（分割代入では self の drop が残る）
+            #(let #idents = unsafe { std::ptr::read(&self.#idents) };)*
+            std::mem::forget(self);
+            #original_ident {
+                #(#build_fields,)*
+            }
+        }
+    })
+}
+
+/// generate the statement which reads PREFIX_FIELD_NAME into the builder field
+fn generate_env_reader(field: &syn::Field, separator: &str) -> proc_macro2::TokenStream {
+    let ident = &field.ident;
+    let name = ident.as_ref().unwrap().unraw().to_string().to_uppercase();
+
+    let parsed = match unwrap_ty(&field.ty) {
+        InnerType::Vec(inner_ty) => quote! {
+            value
+                .split(#separator)
+                .filter(|item| !item.is_empty())
+                .map(|item| item.parse::<#inner_ty>())
+                .collect::<std::result::Result<std::vec::Vec<_>, _>>()
+        },
+        InnerType::Option(inner_ty) => quote! { value.parse::<#inner_ty>() },
+        InnerType::Primitive => {
+            let ty = &field.ty;
+            quote! { value.parse::<#ty>() }
+        }
+    };
+
+    quote! {
+        let key = if prefix.is_empty() {
+            #name.to_owned()
+        } else {
+            format!("{}_{}", prefix, #name)
+        };
+        match std::env::var(&key) {
+            std::result::Result::Ok(value) => {
+                let value = #parsed.map_err(|err| format!("failed to parse {}: {}", key, err))?;
+                builder.#ident = std::option::Option::Some(value);
+            }
+            std::result::Result::Err(std::env::VarError::NotPresent) => {}
+            std::result::Result::Err(err) => return std::result::Result::Err(format!("{}: {}", key, err).into()),
+        }
+    }
+}
+
 #[proc_macro_derive(Builder, attributes(builder))]
 pub fn derive(input: TokenStream) -> TokenStream {
     let parsed = parse_macro_input!(input as DeriveInput);
@@ -100,18 +265,43 @@ pub fn derive(input: TokenStream) -> TokenStream {
     let original_ident = parsed.ident;
     let builder_ident = format_ident!("{}Builder", original_ident);
     let named = extract_named_fields(&parsed.data);
+    let options = match extract_builder_options(&parsed.attrs) {
+        Ok(options) => options,
+        Err(err) => return err.to_compile_error().into(),
+    };
+
+    // 入力に存在しないフィールドは None のままにしておき、必須フィールドの検証は build() に任せる
+    let serde_derive = match (options.serde, cfg!(feature = "serde")) {
+        (true, true) => quote! { #[derive(::serde::Deserialize)] },
+        (true, false) => {
+            return syn::Error::new(
+                original_ident.span(),
+                "`builder(serde)` requires the `serde` feature of derive_builder",
+            )
+            .to_compile_error()
+            .into()
+        }
+        (false, _) => quote! {},
+    };
+    let serde_field_attr = if options.serde {
+        quote! { #[serde(default)] }
+    } else {
+        quote! {}
+    };
 
     let builder_fields = named.iter().map(|f| {
         let ident = &f.ident;
         let ty = &f.ty;
 
         match unwrap_ty(ty) {
-            InnerType::OptionType(_) => {
+            InnerType::Option(_) => {
                 quote! {
+                    #serde_field_attr
                     #ident: #ty
                 }
             }
             _ => quote! {
+                #serde_field_attr
                 #ident: std::option::Option<#ty>
             },
         }
@@ -122,7 +312,7 @@ pub fn derive(input: TokenStream) -> TokenStream {
         let ty = &f.ty;
 
         match unwrap_ty(ty) {
-            InnerType::VecType(inner_ty) => {
+            InnerType::Vec(inner_ty) => {
                 let default_setter = generate_default_setter_with(ident, ty);
 
                 match unwrap_builder_attr_value(&f.attrs) {
@@ -140,27 +330,50 @@ pub fn derive(input: TokenStream) -> TokenStream {
                         };
 
                         if ident.clone().unwrap() == each_ident {
-                            return vec_setters;
+                            vec_setters
                         } else {
-                            return quote! {
+                            quote! {
                                 #vec_setters
                                 #default_setter
-                            };
+                            }
                         }
                     }
                     Some(ParseBuilderAttributeResult::Invalid(meta)) => {
-                        return syn::Error::new_spanned(meta, "expected `builder(each = \"...\")`")
+                        syn::Error::new_spanned(meta, "expected `builder(each = \"...\")`")
                             .to_compile_error()
-                            .into()
                     }
-                    None => return default_setter,
-                };
+                    None => default_setter,
+                }
             }
-            InnerType::OptionType(inner_ty) => generate_default_setter_with(ident, &inner_ty),
-            InnerType::PrimitiveType => generate_default_setter_with(ident, ty),
+            InnerType::Option(inner_ty) => generate_default_setter_with(ident, &inner_ty),
+            InnerType::Primitive => generate_default_setter_with(ident, ty),
         }
     });
 
+    let from_env = if options.from_env {
+        let separator = options
+            .separator
+            .as_ref()
+            .map_or_else(|| ",".to_owned(), syn::LitStr::value);
+        let env_readers = named.iter().map(|f| generate_env_reader(f, &separator));
+
+        quote! {
+            #[automatically_derived]
+            #[allow(dead_code)]
+            impl #builder_ident {
+                /// Reads every field from the `PREFIX_FIELD_NAME` environment variable.
+                /// Variables which are not present leave the field unset.
+                pub fn from_env(prefix: &str) -> std::result::Result<Self, std::boxed::Box<dyn std::error::Error>> {
+                    let mut builder = #original_ident::builder();
+                    #(#env_readers)*
+                    std::result::Result::Ok(builder)
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
     let builder_init = named.iter().map(|f| {
         let ident = &f.ident;
         quote! {
@@ -168,29 +381,49 @@ pub fn derive(input: TokenStream) -> TokenStream {
         }
     });
 
-    let build_fields = named.iter().map(|f| {
-        let ident = &f.ident;
-        let ty = &f.ty;
+    let build_fields = named
+        .iter()
+        .map(|f| {
+            let ident = &f.ident;
+            let ty = &f.ty;
 
-        match unwrap_ty(ty) {
-            InnerType::OptionType(_) => quote! {
-                #ident: self.#ident.take()
-            },
-            InnerType::VecType(_) => quote! {
-                #ident: self.#ident.take().unwrap_or_else(Vec::new)
-            },
-            InnerType::PrimitiveType => quote! {
-                #ident: self.#ident.take().ok_or(format!("{} is not set", stringify!(#ident)))?
-            },
-        }
-    });
+            match unwrap_ty(ty) {
+                InnerType::Option(_) => quote! {
+                    #ident: self.#ident.take()
+                },
+                InnerType::Vec(_) => quote! {
+                    #ident: self.#ident.take().unwrap_or_else(Vec::new)
+                },
+                InnerType::Primitive => quote! {
+                    #ident: self.#ident.take().ok_or(format!("{} is not set", stringify!(#ident)))?
+                },
+            }
+        })
+        .collect::<Vec<_>>();
 
-    let expanded = quote! {
-        pub struct #builder_ident {
-            #(#builder_fields,)*
+    // 必須フィールドが存在しなければ build() が失敗することはないので、Result を経由しない build を追加する
+    let has_required_fields = named
+        .iter()
+        .any(|f| matches!(unwrap_ty(&f.ty), InnerType::Primitive));
+    let build_infallible = if has_required_fields {
+        quote! {}
+    } else {
+        quote! {
+            fn build_infallible(&mut self) -> #original_ident {
+                #original_ident {
+                    #(#build_fields,)*
+                }
+            }
         }
+    };
 
-        impl #builder_ident {
+    let (builder_impl, constness) = if options.const_fn {
+        match generate_const_builder_impl(&original_ident, named) {
+            Ok(builder_impl) => (builder_impl, quote! { const }),
+            Err(err) => return err.to_compile_error().into(),
+        }
+    } else {
+        let builder_impl = quote! {
             #(#builder_setters)*
 
             fn build(&mut self) -> std::result::Result<#original_ident, std::boxed::Box<dyn std::error::Error>> {
@@ -198,15 +431,62 @@ pub fn derive(input: TokenStream) -> TokenStream {
                     #(#build_fields,)*
                 })
             }
+
+            #build_infallible
+
+            /// Panics with the name of the missing field instead of returning an error.
+            #[track_caller]
+            fn build_or_panic(&mut self) -> #original_ident {
+                match self.build() {
+                    std::result::Result::Ok(value) => value,
+                    std::result::Result::Err(err) => {
+                        panic!("failed to build {}: {}", stringify!(#original_ident), err)
+                    }
+                }
+            }
+        };
+        (builder_impl, quote! {})
+    };
+
+    // 元の構造体の #[debug] 属性に合わせて、設定済みの値だけを表示する
+    let debug_impl = if options.debug {
+        match debug::generate_debug_impl(&builder_ident, named) {
+            Ok(debug_impl) => debug_impl,
+            Err(err) => return err.to_compile_error().into(),
         }
+    } else {
+        quote! {}
+    };
 
+    // 呼び出されなかったセッターなどで利用側に dead_code の警告が出ないようにする
+    let expanded = quote! {
+        #serde_derive
+        #[must_use = "builders do nothing unless `build()` is called"]
+        #[allow(dead_code)]
+        pub struct #builder_ident {
+            #(#builder_fields,)*
+        }
+
+        #[automatically_derived]
+        #[allow(dead_code)]
+        impl #builder_ident {
+            #builder_impl
+        }
+
+        #[automatically_derived]
+        #[allow(dead_code)]
         impl #original_ident {
-            pub fn builder() -> #builder_ident {
+            #[must_use = "builders do nothing unless `build()` is called"]
+            pub #constness fn builder() -> #builder_ident {
                 #builder_ident {
                     #(#builder_init,)*
                 }
             }
         }
+
+        #from_env
+
+        #debug_impl
     };
 
     expanded.into()
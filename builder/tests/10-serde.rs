@@ -0,0 +1,40 @@
+// With #[builder(serde)] the generated builder implements serde::Deserialize,
+// so a partial configuration can be loaded from JSON or TOML, adjusted through
+// the setters and then validated by build().
+//
+// Every field is optional in the input. Fields that are missing stay unset in
+// the builder, and required fields that were never set are reported by the
+// usual build() error.
+//
+// This test requires the `serde` feature of derive_builder.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+#[builder(serde)]
+pub struct Command {
+    executable: String,
+    #[builder(each = "arg")]
+    args: Vec<String>,
+    env: Vec<String>,
+    current_dir: Option<String>,
+}
+
+fn main() {
+    let mut builder: CommandBuilder =
+        serde_json::from_str(r#"{ "args": ["build"], "current_dir": ".." }"#).unwrap();
+    let command = builder
+        .executable("cargo".to_owned())
+        .arg("--release".to_owned())
+        .build()
+        .unwrap();
+
+    assert_eq!(command.executable, "cargo");
+    assert_eq!(command.args, vec!["build", "--release"]);
+    assert!(command.env.is_empty());
+    assert_eq!(command.current_dir, Some("..".to_owned()));
+
+    let mut builder: CommandBuilder = serde_json::from_str("{}").unwrap();
+    let err = builder.build().err().unwrap();
+    assert_eq!(err.to_string(), "executable is not set");
+}
@@ -0,0 +1,49 @@
+// With #[builder(from_env)] the builder gets a `from_env(prefix)` constructor
+// which reads each field from the `PREFIX_FIELD_NAME` environment variable and
+// parses it via FromStr.
+//
+// Vec fields are split on a separator, which defaults to "," and can be
+// changed with #[builder(separator = "...")]. Variables which are not present
+// leave the field unset, so required fields are still reported by build(), and
+// values which fail to parse are reported through the builder error type.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+#[builder(from_env, separator = ":")]
+pub struct Server {
+    host: String,
+    port: u16,
+    #[builder(each = "path")]
+    paths: Vec<String>,
+    workers: Option<usize>,
+}
+
+fn main() {
+    std::env::set_var("APP_HOST", "localhost");
+    std::env::set_var("APP_PORT", "8080");
+    std::env::set_var("APP_PATHS", "/usr/bin:/bin");
+
+    let server = ServerBuilder::from_env("APP")
+        .unwrap()
+        .path("/sbin".to_owned())
+        .build()
+        .unwrap();
+
+    assert_eq!(server.host, "localhost");
+    assert_eq!(server.port, 8080);
+    assert_eq!(server.paths, vec!["/usr/bin", "/bin", "/sbin"]);
+    assert_eq!(server.workers, None);
+
+    std::env::set_var("APP_WORKERS", "four");
+    let err = ServerBuilder::from_env("APP").err().unwrap();
+    assert_eq!(
+        err.to_string(),
+        "failed to parse APP_WORKERS: invalid digit found in string"
+    );
+
+    std::env::remove_var("APP_HOST");
+    std::env::set_var("APP_WORKERS", "4");
+    let err = ServerBuilder::from_env("APP").unwrap().build().err().unwrap();
+    assert_eq!(err.to_string(), "host is not set");
+}
@@ -0,0 +1,32 @@
+// The generated code should not trigger warnings in the caller's crate, even
+// when some of the generated setters are never called.
+//
+// Every generated impl is marked #[automatically_derived], and dead_code is
+// allowed on the builder so that unused setters, from_env or build() do not
+// show up as warnings.
+
+#![deny(warnings)]
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+#[builder(from_env)]
+pub struct Command {
+    executable: String,
+    #[builder(each = "arg")]
+    args: Vec<String>,
+    env: Vec<String>,
+    current_dir: Option<String>,
+}
+
+fn main() {
+    let command = Command::builder()
+        .executable("cargo".to_owned())
+        .build()
+        .unwrap();
+
+    assert_eq!(command.executable, "cargo");
+    assert!(command.args.is_empty());
+    assert!(command.env.is_empty());
+    assert!(command.current_dir.is_none());
+}
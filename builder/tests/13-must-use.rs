@@ -0,0 +1,15 @@
+// Dropping a builder without calling build() is almost always a bug, so both
+// the builder type and the builder() constructor are marked #[must_use].
+
+#![deny(unused_must_use)]
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+pub struct Command {
+    executable: String,
+}
+
+fn main() {
+    Command::builder();
+}
@@ -0,0 +1,45 @@
+// With #[builder(const)] the builder can be used to define constants and
+// statics. builder(), the setters and build() are generated as const fn, and
+// the setters take and return the builder by value because the usual
+// `&mut self` chaining cannot be finished with a by-value build() in a const
+// context.
+//
+// build() panics when a required field is not set, which turns into a compile
+// error when it is evaluated as part of a constant. All fields have to be
+// const-constructible, so Vec fields are rejected in this mode; a slice such as
+// &'static [T] can be used instead.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+#[builder(const)]
+pub struct Route {
+    path: &'static str,
+    port: u16,
+    retries: Option<u8>,
+    tags: Option<&'static [&'static str]>,
+}
+
+const INDEX: Route = Route::builder().path("/").port(80).build();
+
+static ROUTES: [Route; 2] = [
+    Route::builder().path("/api").port(8080).retries(3).build(),
+    Route::builder().path("/health").port(8081).build(),
+];
+
+fn main() {
+    assert_eq!(INDEX.path, "/");
+    assert_eq!(INDEX.port, 80);
+    assert_eq!(INDEX.retries, None);
+    assert!(INDEX.tags.is_none());
+
+    assert_eq!(ROUTES[0].retries, Some(3));
+    assert_eq!(ROUTES[1].path, "/health");
+
+    let route = Route::builder()
+        .path("/metrics")
+        .port(9090)
+        .tags(&["internal"])
+        .build();
+    assert_eq!(route.tags, Some(&["internal"][..]));
+}
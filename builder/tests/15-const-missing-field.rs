@@ -0,0 +1,17 @@
+// A required field which is not set in builder(const) mode is reported while
+// evaluating the constant.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+#[builder(const)]
+pub struct Route {
+    path: &'static str,
+    port: u16,
+}
+
+const INDEX: Route = Route::builder().path("/").build();
+
+fn main() {
+    assert_eq!(INDEX.path, "/");
+}
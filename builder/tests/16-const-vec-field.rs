@@ -0,0 +1,14 @@
+// Adding items to a Vec cannot be evaluated at compile time, so builder(const)
+// rejects Vec fields with a hint to use a static slice instead.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+#[builder(const)]
+pub struct Route {
+    path: &'static str,
+    #[builder(each = "tag")]
+    tags: Vec<&'static str>,
+}
+
+fn main() {}
@@ -0,0 +1,45 @@
+// When none of the fields is required, i.e. every field is an Option or a Vec,
+// build() can never fail. In that case the builder also gets a
+// build_infallible() method which returns the struct directly instead of a
+// Result that has to be unwrapped.
+//
+// Independently of that, build_or_panic() is always available for tests and
+// panics with a message naming the missing field.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+pub struct Options {
+    #[builder(each = "arg")]
+    args: Vec<String>,
+    current_dir: Option<String>,
+}
+
+#[derive(Builder)]
+pub struct Command {
+    executable: String,
+    current_dir: Option<String>,
+}
+
+fn main() {
+    let options = Options::builder().arg("--release".to_owned()).build_infallible();
+    assert_eq!(options.args, vec!["--release"]);
+    assert_eq!(options.current_dir, None);
+
+    let command = Command::builder()
+        .executable("cargo".to_owned())
+        .build_or_panic();
+    assert_eq!(command.executable, "cargo");
+    assert_eq!(command.current_dir, None);
+
+    std::panic::set_hook(Box::new(|_| {}));
+    let err = std::panic::catch_unwind(|| {
+        Command::builder().current_dir("..".to_owned()).build_or_panic()
+    })
+    .err()
+    .unwrap();
+    assert_eq!(
+        err.downcast_ref::<String>().unwrap(),
+        "failed to build Command: executable is not set"
+    );
+}
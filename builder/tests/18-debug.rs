@@ -0,0 +1,66 @@
+// With #[builder(debug)] the builder implements Debug, which is handy when a
+// half-configured builder is logged or shows up in an assertion message.
+// Fields which have been set are printed with their value and the others as
+// <unset>.
+//
+// The builder honors the #[debug] attributes that CustomDebug reads from the
+// struct, so a redacted password stays redacted in the builder as well,
+// skipped fields are left out and renamed fields keep their new name. Every
+// formatting attribute of CustomDebug applies, except that
+// #[debug(expr = "...")] can refer only to the field itself, since `self` is
+// the builder.
+
+use derive_builder::Builder;
+use derive_debug::CustomDebug;
+
+#[derive(Builder, CustomDebug)]
+#[builder(debug)]
+pub struct Connection {
+    host: String,
+    #[debug = "0x{:04x}"]
+    port: u16,
+    #[debug(rename = "user")]
+    username: String,
+    #[debug(redact)]
+    password: String,
+    #[debug(redact(len))]
+    token: Vec<u8>,
+    #[debug = "{:?} sec"]
+    timeout: Option<u64>,
+    #[debug(skip)]
+    #[allow(dead_code)]
+    retries: u32,
+    #[debug(expr = "certificate.len()")]
+    certificate: String,
+    #[debug(bytes)]
+    buffer_size: usize,
+}
+
+fn main() {
+    let mut builder = Connection::builder();
+    builder.host("localhost".to_owned());
+    assert_eq!(
+        format!("{:?}", builder),
+        r#"ConnectionBuilder { host: "localhost", port: <unset>, user: <unset>, password: <unset>, token: <unset>, timeout: <unset>, certificate: <unset>, buffer_size: <unset>, .. }"#
+    );
+
+    builder
+        .port(8080)
+        .username("admin".to_owned())
+        .password("hunter2".to_owned())
+        .token(b"secret".to_vec())
+        .timeout(30)
+        .retries(3)
+        .certificate("-----BEGIN CERTIFICATE-----".to_owned())
+        .buffer_size(64 * 1024);
+    assert_eq!(
+        format!("{:?}", builder),
+        r#"ConnectionBuilder { host: "localhost", port: 0x1f90, user: "admin", password: [REDACTED], token: [REDACTED; len=6], timeout: Some(30) sec, certificate: 27, buffer_size: 64.0 KiB, .. }"#
+    );
+
+    let connection = builder.build().unwrap();
+    assert_eq!(
+        format!("{:?}", connection),
+        r#"Connection { host: "localhost", port: 0x1f90, user: "admin", password: [REDACTED], token: [REDACTED; len=6], timeout: Some(30) sec, certificate: 27, buffer_size: 64.0 KiB, .. }"#
+    );
+}
@@ -0,0 +1,37 @@
+// Fields of builder(const) are not limited to Copy types. A field with a
+// destructor such as String can be set and built in const fn, since neither
+// the setters nor build() drop a value. A setter never replaces a value set
+// before, so nothing is leaked either: setting a field twice panics, which is
+// a compile error in a const context.
+//
+// The builder is still usable at runtime, where the same setters and build()
+// are called on heap allocated values.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+#[builder(const)]
+pub struct Service {
+    name: String,
+    description: Option<String>,
+    port: u16,
+}
+
+const EMPTY: Service = Service::builder()
+    .name(String::new())
+    .port(80)
+    .build();
+
+fn main() {
+    assert_eq!(EMPTY.name, "");
+    assert_eq!(EMPTY.description, None);
+
+    let service = Service::builder()
+        .name("api".to_owned())
+        .description("public api".to_owned())
+        .port(8080)
+        .build();
+    assert_eq!(service.name, "api");
+    assert_eq!(service.description.as_deref(), Some("public api"));
+    assert_eq!(service.port, 8080);
+}
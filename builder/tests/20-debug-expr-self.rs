@@ -0,0 +1,35 @@
+// Inside the Debug impl of the builder `self` is the builder, whose fields are
+// all wrapped in Option, and only the field being printed is bound by name, as
+// the other fields may not be set yet. So #[debug(expr = "...")] referring to
+// `self` or to another field cannot be evaluated the way CustomDebug does, and
+// is rejected by #[builder(debug)].
+
+use derive_builder::Builder;
+use derive_debug::CustomDebug;
+
+#[derive(Builder, CustomDebug)]
+#[builder(debug)]
+pub struct Buffer {
+    #[debug(expr = "buf.len() * 100 / self.capacity")]
+    buf: Vec<u8>,
+    capacity: usize,
+}
+
+#[derive(Builder, CustomDebug)]
+#[builder(debug)]
+pub struct Usage {
+    #[debug(expr = "used.len() * 100 / capacity")]
+    used: Vec<u8>,
+    capacity: usize,
+}
+
+// Method calls and paths which happen to share a name with a field are fine.
+#[derive(Builder, CustomDebug)]
+#[builder(debug)]
+pub struct Queue {
+    #[debug(expr = "items.len()")]
+    items: Vec<u8>,
+    len: usize,
+}
+
+fn main() {}
@@ -0,0 +1,37 @@
+// The hex, bin and oct shorthands of CustomDebug apply to the builder as well,
+// so a set register prints the same way as the struct built from it.
+
+use derive_builder::Builder;
+use derive_debug::CustomDebug;
+
+#[derive(Builder, CustomDebug)]
+#[builder(debug)]
+pub struct Register {
+    #[debug(hex(4))]
+    address: u16,
+    #[debug(bin(4))]
+    flags: u8,
+    #[debug(oct)]
+    mode: u32,
+}
+
+fn main() {
+    let mut builder = Register::builder();
+    builder.flags(0b101);
+    assert_eq!(
+        format!("{:?}", builder),
+        "RegisterBuilder { address: <unset>, flags: 0b0101, mode: <unset> }"
+    );
+
+    builder.address(0xbeef).mode(0o755);
+    assert_eq!(
+        format!("{:?}", builder),
+        "RegisterBuilder { address: 0xbeef, flags: 0b0101, mode: 0o755 }"
+    );
+
+    let register = builder.build().unwrap();
+    assert_eq!(
+        format!("{:?}", register),
+        "Register { address: 0xbeef, flags: 0b0101, mode: 0o755 }"
+    );
+}
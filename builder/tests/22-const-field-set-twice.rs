@@ -0,0 +1,18 @@
+// Setting a field twice in builder(const) mode would have to forget the first
+// value, since it cannot be dropped in const fn. The setter panics instead,
+// which is reported while evaluating the constant.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+#[builder(const)]
+pub struct Route {
+    path: &'static str,
+    port: u16,
+}
+
+const INDEX: Route = Route::builder().path("/").path("/index").port(80).build();
+
+fn main() {
+    assert_eq!(INDEX.path, "/");
+}
@@ -10,4 +10,18 @@ fn tests() {
     t.pass("tests/07-repeated-field.rs");
     t.compile_fail("tests/08-unrecognized-attribute.rs");
     t.pass("tests/09-redefined-prelude-types.rs");
+    #[cfg(feature = "serde")]
+    t.pass("tests/10-serde.rs");
+    t.pass("tests/11-from-env.rs");
+    t.pass("tests/12-lint-hygiene.rs");
+    t.compile_fail("tests/13-must-use.rs");
+    t.pass("tests/14-const-builder.rs");
+    t.compile_fail("tests/15-const-missing-field.rs");
+    t.compile_fail("tests/16-const-vec-field.rs");
+    t.pass("tests/17-infallible-build.rs");
+    t.pass("tests/18-debug.rs");
+    t.pass("tests/19-const-drop-fields.rs");
+    t.compile_fail("tests/20-debug-expr-self.rs");
+    t.pass("tests/21-debug-number-formats.rs");
+    t.compile_fail("tests/22-const-field-set-twice.rs");
 }
@@ -0,0 +1,148 @@
+use proc_macro2::Span;
+use syn::spanned::Spanned;
+
+use derive_support::debug::{parse_bound, parse_limit};
+use derive_support::set_once;
+
+/// Values given by the struct, enum or union level #[debug(...)] attributes
+#[derive(Default)]
+pub(crate) struct ContainerAttributes {
+    pub(crate) bound: Option<Vec<syn::WherePredicate>>,
+    // エラーの位置を示すために属性のスパンを保持する
+    pub(crate) transparent: Option<Span>,
+    pub(crate) fmt: Option<syn::LitStr>,
+    /// The default of `max_items` for the fields of a sequence type
+    pub(crate) max_items: Option<syn::LitInt>,
+    /// The default of `max_len` for the fields of a string type
+    pub(crate) max_len: Option<syn::LitInt>,
+    pub(crate) visit_fields: Option<Span>,
+    pub(crate) cycle_safe: Option<Span>,
+    /// #[debug(unsafe(union_as = "field"))], the field of a union which is printed
+    pub(crate) union_as: Option<syn::LitStr>,
+    pub(crate) opaque: Option<Span>,
+}
+
+/// Values given by the #[debug(...)] attributes of an enum variant
+#[derive(Default)]
+pub(crate) struct VariantAttributes {
+    pub(crate) fmt: Option<syn::LitStr>,
+}
+
+/// Parse the debug attributes of the struct or enum itself.
+pub(crate) fn extract_container_attributes(
+    attrs: &[syn::Attribute],
+) -> syn::Result<ContainerAttributes> {
+    let mut container_attrs = ContainerAttributes::default();
+
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("debug")) {
+        attr.meta.require_list()?;
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("bound") {
+                set_once(
+                    &mut container_attrs.bound,
+                    parse_bound(&meta)?,
+                    &meta.path,
+                    "`bound` debug attribute",
+                )
+            } else if meta.path.is_ident("transparent") {
+                let span = meta.path.span();
+                set_once(
+                    &mut container_attrs.transparent,
+                    span,
+                    &meta.path,
+                    "`transparent` debug attribute",
+                )
+            } else if meta.path.is_ident("fmt") {
+                set_once(
+                    &mut container_attrs.fmt,
+                    meta.value()?.parse()?,
+                    &meta.path,
+                    "`fmt` debug attribute",
+                )
+            } else if meta.path.is_ident("unsafe") {
+                // 共用体のフィールドが有効かどうかは利用側が保証するので、unsafe(...) で明示させる
+                meta.parse_nested_meta(|inner| {
+                    if inner.path.is_ident("union_as") {
+                        set_once(
+                            &mut container_attrs.union_as,
+                            inner.value()?.parse()?,
+                            &inner.path,
+                            "`union_as` debug attribute",
+                        )
+                    } else {
+                        Err(inner.error("expected `unsafe(union_as = \"...\")`"))
+                    }
+                })
+            } else if meta.path.is_ident("union_as") {
+                Err(meta.error(
+                    "`union_as` reads the union without knowing which field is valid, write `unsafe(union_as = \"...\")`",
+                ))
+            } else if meta.path.is_ident("opaque") {
+                let span = meta.path.span();
+                set_once(
+                    &mut container_attrs.opaque,
+                    span,
+                    &meta.path,
+                    "`opaque` debug attribute",
+                )
+            } else if meta.path.is_ident("cycle_safe") {
+                let span = meta.path.span();
+                set_once(
+                    &mut container_attrs.cycle_safe,
+                    span,
+                    &meta.path,
+                    "`cycle_safe` debug attribute",
+                )
+            } else if meta.path.is_ident("visit_fields") {
+                let span = meta.path.span();
+                set_once(
+                    &mut container_attrs.visit_fields,
+                    span,
+                    &meta.path,
+                    "`visit_fields` debug attribute",
+                )
+            } else if meta.path.is_ident("max_items") {
+                set_once(
+                    &mut container_attrs.max_items,
+                    parse_limit(&meta)?,
+                    &meta.path,
+                    "`max_items` debug attribute",
+                )
+            } else if meta.path.is_ident("max_len") {
+                set_once(
+                    &mut container_attrs.max_len,
+                    parse_limit(&meta)?,
+                    &meta.path,
+                    "`max_len` debug attribute",
+                )
+            } else {
+                Err(meta.error("unknown debug attribute"))
+            }
+        })?;
+    }
+    Ok(container_attrs)
+}
+
+/// Parse the debug attributes of an enum variant.
+pub(crate) fn extract_variant_attributes(
+    attrs: &[syn::Attribute],
+) -> syn::Result<VariantAttributes> {
+    let mut variant_attrs = VariantAttributes::default();
+
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("debug")) {
+        attr.meta.require_list()?;
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("fmt") {
+                set_once(
+                    &mut variant_attrs.fmt,
+                    meta.value()?.parse()?,
+                    &meta.path,
+                    "`fmt` debug attribute",
+                )
+            } else {
+                Err(meta.error("unknown debug attribute"))
+            }
+        })?;
+    }
+    Ok(variant_attrs)
+}
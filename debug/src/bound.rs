@@ -0,0 +1,103 @@
+use std::collections::HashSet;
+use syn::visit::{self, Visit};
+
+/// Walks a field type and records how the type parameters of the input are used.
+struct TypeParamVisitor<'a> {
+    type_params: &'a HashSet<syn::Ident>,
+    // フィールドの型に直接現れる型パラメータ（T や Vec<T> など）
+    used: Vec<syn::Ident>,
+    // 型パラメータの関連型（T::Value や <T as Trait>::Value など）
+    associated: Vec<syn::TypePath>,
+}
+
+impl<'ast> Visit<'ast> for TypeParamVisitor<'_> {
+    fn visit_type_path(&mut self, ty: &'ast syn::TypePath) {
+        // PhantomData<T> は T に関係なく Debug を実装しているので、その内側は無視する
+        if let Some(last) = ty.path.segments.last() {
+            if last.ident == "PhantomData" {
+                return;
+            }
+        }
+
+        let first_is_param = match &ty.qself {
+            Some(qself) => match &*qself.ty {
+                syn::Type::Path(inner) => inner.qself.is_none() && self.is_type_param(&inner.path),
+                _ => false,
+            },
+            None => ty.path.segments.len() > 1 && self.is_type_param(&ty.path),
+        };
+        if first_is_param {
+            if !self.associated.contains(ty) {
+                self.associated.push(ty.clone());
+            }
+            return;
+        }
+
+        if ty.qself.is_none() && ty.path.segments.len() == 1 {
+            let ident = &ty.path.segments[0].ident;
+            if self.type_params.contains(ident) && !self.used.contains(ident) {
+                self.used.push(ident.clone());
+            }
+        }
+
+        visit::visit_type_path(self, ty);
+    }
+}
+
+impl TypeParamVisitor<'_> {
+    fn is_type_param(&self, path: &syn::Path) -> bool {
+        path.leading_colon.is_none()
+            && path
+                .segments
+                .first()
+                .is_some_and(|segment| self.type_params.contains(&segment.ident))
+    }
+}
+
+/// Infer the bounds on `trait_path` (usually `Debug`) required to format a field of the given type.
+///
+/// A type parameter `T` is bounded as `T: Debug` unless it only appears inside
+/// `PhantomData`, and associated types such as `T::Value` are bounded directly
+/// instead of `T`.
+pub(crate) fn infer_bounds(
+    generics: &syn::Generics,
+    ty: &syn::Type,
+    trait_path: &syn::Path,
+) -> Vec<syn::WherePredicate> {
+    let type_params = generics
+        .type_params()
+        .map(|param| param.ident.clone())
+        .collect::<HashSet<_>>();
+
+    let mut visitor = TypeParamVisitor {
+        type_params: &type_params,
+        used: vec![],
+        associated: vec![],
+    };
+    visitor.visit_type(ty);
+
+    let params = visitor
+        .used
+        .iter()
+        .map(|ident| syn::parse_quote!(#ident: #trait_path));
+    let associated = visitor
+        .associated
+        .iter()
+        .map(|path| syn::parse_quote!(#path: #trait_path));
+
+    params.chain(associated).collect()
+}
+
+/// Append the bounds to the where-clause of generics, skipping duplicates.
+pub(crate) fn add_bounds(
+    mut generics: syn::Generics,
+    bounds: impl IntoIterator<Item = syn::WherePredicate>,
+) -> syn::Generics {
+    let where_clause = generics.make_where_clause();
+    for bound in bounds {
+        if !where_clause.predicates.iter().any(|p| p == &bound) {
+            where_clause.predicates.push(bound);
+        }
+    }
+    generics
+}
@@ -1,103 +1,615 @@
 use proc_macro::TokenStream;
+use proc_macro2::Span;
 use quote::quote;
-use syn::{parse_macro_input, spanned::Spanned, DeriveInput};
-
-fn extract_debug_attributes(
-    attrs: &[syn::Attribute],
-) -> Result<Vec<String>, proc_macro2::TokenStream> {
-    let mut attrs_values = vec![];
-
-    for attr in attrs {
-        match &attr.meta {
-            syn::Meta::NameValue(ref named) if named.path.is_ident("debug") => {
-                if let syn::Expr::Lit(syn::ExprLit {
-                    lit: syn::Lit::Str(litstr),
-                    ..
-                }) = &named.value
-                {
-                    attrs_values.push(litstr.value());
-                }
+use syn::{ext::IdentExt, parse_macro_input, DeriveInput};
+
+use attr::{extract_container_attributes, extract_variant_attributes, ContainerAttributes};
+use derive_support::debug::{
+    extract_debug_attributes, generate_value, generate_wrappers, FieldAttributes, FieldFormat,
+    Redaction,
+};
+use derive_support::{field, format};
+
+mod attr;
+mod bound;
+
+/// Derive `Debug`, customized by the `#[debug(...)]` attributes.
+///
+/// # Bounds
+///
+/// The type parameters used by a printed field get a `Debug` bound. A field with
+/// `#[debug = "..."]` or one of its shorthands such as `#[debug(hex)]` gets the trait of its
+/// placeholder instead, e.g. `LowerHex` for `"0x{:x}"` and `Display` for `"{}"`.
+/// `#[debug(bound = "...")]` replaces the inferred bounds.
+///
+/// # Unions
+///
+/// Which field of a union is valid is not known, so a union needs one of two attributes.
+/// `#[debug(opaque)]` prints only the size of the union. `#[debug(unsafe(union_as = "field"))]`
+/// prints the named field, which the generated impl reads whatever field was written last.
+///
+/// # Safety
+///
+/// With `unsafe(union_as = "field")` the caller asserts that every value the union can hold is
+/// a valid value of `field`. This holds for integers, floats and arrays of them when the field
+/// covers the whole union, but not for `bool`, `char`, references, enums or any other type with
+/// invalid bit patterns. Reading such a field after another one was written is undefined
+/// behavior.
+#[proc_macro_derive(CustomDebug, attributes(debug))]
+pub fn derive(input: TokenStream) -> TokenStream {
+    let parsed = parse_macro_input!(input as DeriveInput);
+
+    match expand(parsed) {
+        Ok(expanded) => expanded.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+/// A field of the struct or of an enum variant together with its debug attributes
+struct DebugField<'a> {
+    field: &'a syn::Field,
+    attrs: FieldAttributes,
+    binding: syn::Ident,
+    /// Whether the format was taken from `max_items` or `max_len` of the struct
+    default_format: bool,
+}
+
+/// The last segment of a type path, looking through references
+fn last_type_segment(ty: &syn::Type) -> Option<&syn::Ident> {
+    match ty {
+        syn::Type::Reference(reference) => last_type_segment(&reference.elem),
+        syn::Type::Path(path) => path.path.segments.last().map(|segment| &segment.ident),
+        _ => None,
+    }
+}
+
+/// Whether `max_items` of the struct applies to the field, e.g. `Vec<T>`, `[T; N]` or `&[T]`
+fn is_sequence(ty: &syn::Type) -> bool {
+    match ty {
+        syn::Type::Reference(reference) => is_sequence(&reference.elem),
+        syn::Type::Array(_) | syn::Type::Slice(_) => true,
+        _ => last_type_segment(ty)
+            .is_some_and(|ident| ident == "Vec" || ident == "VecDeque" || ident == "LinkedList"),
+    }
+}
+
+/// Whether `max_len` of the struct applies to the field, e.g. `String` or `&str`
+fn is_string(ty: &syn::Type) -> bool {
+    last_type_segment(ty).is_some_and(|ident| ident == "String" || ident == "str")
+}
+
+fn extract_debug_fields<'a>(
+    fields: &'a syn::Fields,
+    container_attrs: &ContainerAttributes,
+) -> syn::Result<Vec<DebugField<'a>>> {
+    fields
+        .iter()
+        .enumerate()
+        .map(|(i, field)| {
+            let binding = field::binding(field, i);
+            let mut attrs = extract_debug_attributes(field)?;
+            let mut default_format = false;
+            // 構造体に指定された上限は、書式の指定がない列や文字列のフィールドにだけ適用する
+            if attrs.format.is_none() && attrs.alt.is_none() && !attrs.skip {
+                attrs.format = match (&container_attrs.max_items, &container_attrs.max_len) {
+                    (Some(max_items), _) if is_sequence(&field.ty) => {
+                        Some(FieldFormat::MaxItems(max_items.clone()))
+                    }
+                    (_, Some(max_len)) if is_string(&field.ty) => {
+                        Some(FieldFormat::MaxLen(max_len.clone()))
+                    }
+                    _ => None,
+                };
+                default_format = attrs.format.is_some();
             }
-            _ => {
-                return Err(
-                    syn::Error::new(attr.span(), "only debug attributes can be applied")
-                        .to_compile_error(),
-                )
+            if let (None, Some(rename)) = (&field.ident, &attrs.rename) {
+                return Err(syn::Error::new_spanned(
+                    rename,
+                    "`rename` can only be used on named fields",
+                ));
             }
+            Ok(DebugField {
+                field,
+                attrs,
+                binding,
+                default_format,
+            })
+        })
+        .collect()
+}
+
+/// Generate the pattern which binds every field by reference, e.g. `Self::Variant { a, b }`.
+fn generate_pattern(
+    path: proc_macro2::TokenStream,
+    fields: &syn::Fields,
+    debug_fields: &[DebugField],
+) -> proc_macro2::TokenStream {
+    let bindings = debug_fields.iter().map(|f| &f.binding);
+
+    match fields {
+        syn::Fields::Named(_) => quote! { #path { #(ref #bindings),* } },
+        syn::Fields::Unnamed(_) => quote! { #path ( #(ref #bindings),* ) },
+        syn::Fields::Unit => quote! { #path },
+    }
+}
+
+/// The bounds required by a field, unless they are given by `debug(bound = "...")`.
+fn infer_field_bounds(generics: &syn::Generics, f: &DebugField) -> Vec<syn::WherePredicate> {
+    // debug(alt = "...") の書式文字列のトレイトも合わせて要求する
+    let with_alt = |trait_path: syn::Path| {
+        let mut traits = vec![trait_path];
+        traits.extend(f.attrs.alt.as_ref().map(format::field_format_trait));
+        traits.dedup();
+        traits
+            .iter()
+            .flat_map(|trait_path| bound::infer_bounds(generics, &f.field.ty, trait_path))
+            .collect()
+    };
+
+    // 出力しないフィールドや値を伏せるフィールドには Debug を要求しない
+    match &f.attrs.format {
+        _ if f.attrs.skip => vec![],
+        Some(FieldFormat::Redact(Redaction::Hash)) => {
+            bound::infer_bounds(generics, &f.field.ty, &syn::parse_quote!(std::hash::Hash))
         }
+        // 書式文字列のトレイトを要求する（`{:x}` なら LowerHex）
+        Some(FieldFormat::Template(format)) => with_alt(format::field_format_trait(format)),
+        // debug(with = "...") の関数や debug(expr = "...") の式が要求する境界は推論できないので
+        // bound 属性で指定する
+        Some(
+            FieldFormat::Redact(_)
+            | FieldFormat::With(_)
+            | FieldFormat::Expr(_)
+            | FieldFormat::MaxLen(_)
+            | FieldFormat::Bytes,
+        ) => vec![],
+        _ => with_alt(syn::parse_quote!(std::fmt::Debug)),
     }
-    Ok(attrs_values)
 }
 
-#[proc_macro_derive(CustomDebug, attributes(debug))]
-pub fn derive(input: TokenStream) -> TokenStream {
-    let parsed = parse_macro_input!(input as DeriveInput);
+/// The printed name of a field. Tuple fields are named by `index`.
+fn field_name(f: &DebugField, index: usize) -> String {
+    match (&f.attrs.rename, &f.field.ident) {
+        (Some(rename), _) => rename.value(),
+        (None, Some(ident)) => ident.unraw().to_string(),
+        (None, None) => index.to_string(),
+    }
+}
 
-    let original_ident = parsed.ident;
-    let named = if let syn::Data::Struct(syn::DataStruct {
-        fields: syn::Fields::Named(syn::FieldsNamed { named, .. }),
-        ..
-    }) = parsed.data
-    {
-        named
+/// Generate the expression which formats the bound fields the same way as `#[derive(Debug)]`.
+fn generate_fmt_body(
+    name: &syn::Ident,
+    fields: &syn::Fields,
+    debug_fields: &[DebugField],
+    formatter: &syn::Ident,
+) -> proc_macro2::TokenStream {
+    let name = name.unraw().to_string();
+    let builder = syn::Ident::new("debug_builder", Span::mixed_site());
+
+    let printed = debug_fields.iter().filter(|f| !f.attrs.skip);
+    let values = printed
+        .clone()
+        .map(|f| generate_value(&f.attrs, &f.binding));
+    // スキップしたフィールドがあれば、省略されていることが分かるように `..` を出力する
+    let finish = if debug_fields.iter().any(|f| f.attrs.skip) {
+        quote! { finish_non_exhaustive }
     } else {
-        return syn::Error::new(
-            original_ident.span(),
-            "CustomDerive can only be applied to struct with named fields",
-        )
-        .to_compile_error()
-        .into();
+        quote! { finish }
     };
 
-    let field_calls = named.iter().map(|f| {
-        let field_ident = &f.ident;
+    match fields {
+        syn::Fields::Named(_) => {
+            let names = printed.map(|f| field_name(f, 0));
+            quote! {
+                let mut #builder = #formatter.debug_struct(#name);
+                #(#builder.field(#names, #values);)*
+                #builder.#finish()
+            }
+        }
+        syn::Fields::Unnamed(_) => quote! {
+            let mut #builder = #formatter.debug_tuple(#name);
+            #(#builder.field(#values);)*
+            #builder.#finish()
+        },
+        syn::Fields::Unit => quote! {
+            #formatter.write_str(#name)
+        },
+    }
+}
 
-        match extract_debug_attributes(&f.attrs) {
-            Ok(values) => match values.first() {
-                Some(attr) => {
-                    quote! {
-                        .field(
-                            stringify!(#field_ident),
-                            &format_args!(#attr, &self.#field_ident))
-                    }
+/// The bounds required by the fields printed with `debug_struct` or `debug_tuple`.
+fn infer_fields_bounds(
+    generics: &syn::Generics,
+    debug_fields: &[DebugField],
+) -> Vec<syn::WherePredicate> {
+    debug_fields
+        .iter()
+        .flat_map(|f| match &f.attrs.bound {
+            Some(bound) => bound.clone(),
+            None => infer_field_bounds(generics, f),
+        })
+        .collect()
+}
+
+/// Generate the output of `debug(fmt = "...")` together with the bounds required by the
+/// fields it prints.
+fn generate_template_body(
+    template: &syn::LitStr,
+    debug_fields: &[DebugField],
+    formatter: &syn::Ident,
+    generics: &syn::Generics,
+) -> syn::Result<(proc_macro2::TokenStream, Vec<syn::WherePredicate>)> {
+    let parsed = format::parse_template(template, "debug(fmt = \"...\")", |arg| {
+        field::find(debug_fields.iter().map(|f| f.field), arg)
+    })?;
+
+    let mut bounds = vec![];
+    for (i, traits) in &parsed.args {
+        let f = &debug_fields[*i];
+        // テンプレートはフィールドの値をそのまま渡すので、redact などが黙って無視されないようにする
+        if f.attrs.skip || f.attrs.alt.is_some() || (f.attrs.format.is_some() && !f.default_format)
+        {
+            let name = match &f.field.ident {
+                Some(ident) => ident.unraw().to_string(),
+                None => i.to_string(),
+            };
+            return Err(syn::Error::new_spanned(
+                template,
+                format!(
+                    "field `{}` has a formatting debug attribute, which `debug(fmt = \"...\")` does not apply",
+                    name
+                ),
+            ));
+        }
+        match &f.attrs.bound {
+            Some(bound) => bounds.extend(bound.iter().cloned()),
+            None => bounds.extend(
+                traits
+                    .iter()
+                    .flat_map(|trait_path| bound::infer_bounds(generics, &f.field.ty, trait_path)),
+            ),
+        }
+    }
+
+    let format = syn::LitStr::new(&parsed.format, template.span());
+    let values = parsed.args.iter().map(|(i, _)| &debug_fields[*i].binding);
+    let body = quote! {
+        #formatter.write_fmt(format_args!(#format, #(#values),*))
+    };
+    Ok((body, bounds))
+}
+
+/// Generate the statements of `debug(cycle_safe)`, which record the address and the type of the
+/// value being formatted in a thread local set and print `<cycle>` instead when it is reached
+/// again from one of its own fields.
+fn generate_cycle_guard(formatter: &syn::Ident) -> proc_macro2::TokenStream {
+    let visiting = syn::Ident::new("__VISITING", Span::mixed_site());
+    let guard = syn::Ident::new("__CycleGuard", Span::mixed_site());
+    let key = syn::Ident::new("key", Span::mixed_site());
+    let guard_value = syn::Ident::new("_guard", Span::mixed_site());
+
+    quote! {
+        std::thread_local! {
+            static #visiting: std::cell::RefCell<std::collections::HashSet<(usize, &'static str)>> =
+                std::cell::RefCell::new(std::collections::HashSet::new());
+        }
+
+        // 出力中に panic しても記録が残らないように、Drop で取り除く
+        struct #guard((usize, &'static str));
+
+        impl Drop for #guard {
+            fn drop(&mut self) {
+                #visiting.with(|visiting| visiting.borrow_mut().remove(&self.0));
+            }
+        }
+
+        // 先頭のフィールドは外側の値と同じアドレスになるので、型も合わせて記録する。
+        // ジェネリックな型の実体化はすべて同じ thread_local を共有するため、型名で区別する
+        let #key = (
+            self as *const Self as *const () as usize,
+            std::any::type_name::<Self>(),
+        );
+        if !#visiting.with(|visiting| visiting.borrow_mut().insert(#key)) {
+            return #formatter.write_str("<cycle>");
+        }
+        let #guard_value = #guard(#key);
+    }
+}
+
+/// Generate `VisitFields` of debug_helpers, which passes every printed field to a visitor.
+fn generate_visit_fields(
+    parsed: &DeriveInput,
+    container_attrs: &ContainerAttributes,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let visitor = syn::Ident::new("visitor", Span::mixed_site());
+    let variants: Vec<_> = match &parsed.data {
+        syn::Data::Struct(syn::DataStruct { fields, .. }) => vec![(quote! { Self }, fields)],
+        syn::Data::Enum(syn::DataEnum { variants, .. }) => variants
+            .iter()
+            .map(|variant| {
+                let variant_ident = &variant.ident;
+                (quote! { Self::#variant_ident }, &variant.fields)
+            })
+            .collect(),
+        // 共用体への debug(visit_fields) は expand でエラーになっている
+        syn::Data::Union(_) => vec![],
+    };
+
+    let mut all_fields = vec![];
+    let mut arms = vec![];
+    for (path, fields) in variants {
+        let debug_fields = extract_debug_fields(fields, container_attrs)?;
+        let pattern = generate_pattern(path, fields, &debug_fields);
+        let visits = debug_fields
+            .iter()
+            .enumerate()
+            .filter(|(_, f)| !f.attrs.skip)
+            .map(|(i, f)| {
+                let name = field_name(f, i);
+                let value = generate_value(&f.attrs, &f.binding);
+                quote! { #visitor.visit_field(#name, #value); }
+            });
+        arms.push(quote! { #pattern => { #(#visits)* } });
+        all_fields.extend(debug_fields);
+    }
+
+    // debug(fmt = "...") の構造体でも、各フィールドは Debug として渡す
+    let bounds = match &container_attrs.bound {
+        Some(bound) => bound.clone(),
+        None => infer_fields_bounds(&parsed.generics, &all_fields),
+    };
+    let generics = bound::add_bounds(parsed.generics.clone(), bounds);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let ident = &parsed.ident;
+    let wrappers = generate_wrappers(all_fields.iter().map(|f| &f.attrs));
+
+    Ok(quote! {
+        impl #impl_generics ::debug_helpers::visit::VisitFields for #ident #ty_generics #where_clause {
+            fn visit_fields(&self, #visitor: &mut dyn ::debug_helpers::visit::FieldVisitor) {
+                #wrappers
+
+                match *self {
+                    #(#arms)*
                 }
-                None => {
-                    quote! {
-                        .field(stringify!(#field_ident), &self.#field_ident)
-                    }
+            }
+        }
+    })
+}
+
+/// Generate the output of a union. Which field is valid cannot be known, so either the field
+/// chosen by `debug(unsafe(union_as = "..."))` is read with `unsafe`, or `debug(opaque)` prints
+/// only the size of the union.
+fn generate_union_body<'a>(
+    name: &syn::Ident,
+    fields: &'a syn::Fields,
+    container_attrs: &ContainerAttributes,
+    formatter: &syn::Ident,
+) -> syn::Result<(proc_macro2::TokenStream, Vec<DebugField<'a>>)> {
+    match (&container_attrs.union_as, container_attrs.opaque) {
+        (Some(_), Some(span)) => Err(syn::Error::new(
+            span,
+            "`debug(opaque)` cannot be combined with `debug(unsafe(union_as = \"...\"))`",
+        )),
+        (None, None) => Err(syn::Error::new(
+            name.span(),
+            "CustomDebug on a union requires `#[debug(unsafe(union_as = \"field\"))]` or `#[debug(opaque)]`",
+        )),
+        (None, Some(_)) => {
+            let name = name.unraw().to_string();
+            let body = quote! {
+                #formatter.write_fmt(format_args!(
+                    "{} {{ <{} bytes> }}",
+                    #name,
+                    std::mem::size_of::<Self>(),
+                ))
+            };
+            Ok((body, vec![]))
+        }
+        (Some(union_as), None) => {
+            let field_ident: syn::Ident = union_as.parse()?;
+            let mut debug_fields = extract_debug_fields(fields, container_attrs)?;
+            if !debug_fields
+                .iter()
+                .any(|f| f.field.ident.as_ref() == Some(&field_ident))
+            {
+                return Err(syn::Error::new_spanned(
+                    union_as,
+                    format!("no field `{}` in the union", field_ident),
+                ));
+            }
+            // 選ばれなかったフィールドは省略されたものとして `..` で示す
+            for f in &mut debug_fields {
+                if f.field.ident.as_ref() != Some(&field_ident) {
+                    f.attrs.skip = true;
                 }
-            },
-            Err(err) => err.into(),
+            }
+
+            let fmt_body = generate_fmt_body(name, fields, &debug_fields, formatter);
+            let body = quote! {
+                // SAFETY: debug(unsafe(union_as = "...")) asserts that the field is valid for
+                // every value of the union
+                let #field_ident = unsafe { &self.#field_ident };
+                #fmt_body
+            };
+            Ok((body, debug_fields))
         }
-    });
+    }
+}
 
-    let generics_type = parsed.generics.params.iter().find_map(|param| {
-        if let syn::GenericParam::Type(ty) = param {
-            Some(&ty.ident)
-        } else {
-            None
+fn expand(parsed: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let original_ident = parsed.ident.clone();
+    let container_attrs = extract_container_attributes(&parsed.attrs)?;
+    let formatter = field::local_ident("f");
+
+    if let (Some(span), syn::Data::Enum(_)) = (container_attrs.transparent, &parsed.data) {
+        return Err(syn::Error::new(
+            span,
+            "`debug(transparent)` can only be applied to struct",
+        ));
+    }
+    if let (Some(fmt), syn::Data::Enum(_)) = (&container_attrs.fmt, &parsed.data) {
+        return Err(syn::Error::new_spanned(
+            fmt,
+            "`debug(fmt = \"...\")` of an enum must be given on each variant",
+        ));
+    }
+    match &parsed.data {
+        syn::Data::Union(_) => {
+            let unsupported = [
+                (container_attrs.transparent, "transparent"),
+                (container_attrs.fmt.as_ref().map(|fmt| fmt.span()), "fmt"),
+                (container_attrs.visit_fields, "visit_fields"),
+            ];
+            if let Some((Some(span), name)) = unsupported.iter().find(|(span, _)| span.is_some()) {
+                return Err(syn::Error::new(
+                    *span,
+                    format!("`debug({})` cannot be applied to union", name),
+                ));
+            }
         }
-    });
+        _ => {
+            let union_only = [
+                (
+                    container_attrs.union_as.as_ref().map(|lit| lit.span()),
+                    "unsafe(union_as)",
+                ),
+                (container_attrs.opaque, "opaque"),
+            ];
+            if let Some((Some(span), name)) = union_only.iter().find(|(span, _)| span.is_some()) {
+                return Err(syn::Error::new(
+                    *span,
+                    format!("`debug({})` can only be applied to union", name),
+                ));
+            }
+        }
+    }
+    if let (Some(span), Some(_)) = (container_attrs.transparent, &container_attrs.fmt) {
+        return Err(syn::Error::new(
+            span,
+            "`debug(transparent)` cannot be combined with `debug(fmt = \"...\")`",
+        ));
+    }
 
-    let debug_impl = if let Some(ty) = generics_type {
-        quote! {
-            impl<#ty: std::fmt::Debug> std::fmt::Debug for #original_ident<#ty>
+    let union_fields = match &parsed.data {
+        syn::Data::Union(syn::DataUnion { fields, .. }) => syn::Fields::Named(fields.clone()),
+        _ => syn::Fields::Unit,
+    };
+    let mut all_fields = vec![];
+    // debug(fmt = "...") のフィールドはプレースホルダのトレイトから境界を推論する
+    let mut inferred_bounds = vec![];
+    let arms = match &parsed.data {
+        syn::Data::Struct(syn::DataStruct { fields, .. }) => {
+            let debug_fields = extract_debug_fields(fields, &container_attrs)?;
+            let pattern = generate_pattern(quote! { Self }, fields, &debug_fields);
+            let body = match (container_attrs.transparent, debug_fields.as_slice()) {
+                // 単一フィールドの構造体は内側の値だけを出力する
+                (Some(_), [field]) if !field.attrs.skip => {
+                    inferred_bounds.extend(infer_fields_bounds(&parsed.generics, &debug_fields));
+                    let value = generate_value(&field.attrs, &field.binding);
+                    quote! { std::fmt::Debug::fmt(#value, #formatter) }
+                }
+                (Some(span), _) => {
+                    return Err(syn::Error::new(
+                        span,
+                        "`debug(transparent)` requires a struct with exactly one field",
+                    ))
+                }
+                (None, _) => match &container_attrs.fmt {
+                    Some(template) => {
+                        let (body, bounds) = generate_template_body(
+                            template,
+                            &debug_fields,
+                            &formatter,
+                            &parsed.generics,
+                        )?;
+                        inferred_bounds.extend(bounds);
+                        body
+                    }
+                    None => {
+                        inferred_bounds
+                            .extend(infer_fields_bounds(&parsed.generics, &debug_fields));
+                        generate_fmt_body(&original_ident, fields, &debug_fields, &formatter)
+                    }
+                },
+            };
+            // テンプレートで出力するフィールドは書式の属性を持たないので、ラッパーは不要
+            if container_attrs.fmt.is_none() {
+                all_fields.extend(debug_fields);
+            }
+            vec![quote! { #pattern => { #body } }]
         }
-    } else {
-        quote! {
-            impl std::fmt::Debug for #original_ident
+        syn::Data::Enum(syn::DataEnum { variants, .. }) => variants
+            .iter()
+            .map(|variant| {
+                let variant_ident = &variant.ident;
+                let variant_attrs = extract_variant_attributes(&variant.attrs)?;
+                let debug_fields = extract_debug_fields(&variant.fields, &container_attrs)?;
+                let pattern = generate_pattern(
+                    quote! { Self::#variant_ident },
+                    &variant.fields,
+                    &debug_fields,
+                );
+                let body = match &variant_attrs.fmt {
+                    Some(template) => {
+                        let (body, bounds) = generate_template_body(
+                            template,
+                            &debug_fields,
+                            &formatter,
+                            &parsed.generics,
+                        )?;
+                        inferred_bounds.extend(bounds);
+                        body
+                    }
+                    None => {
+                        inferred_bounds
+                            .extend(infer_fields_bounds(&parsed.generics, &debug_fields));
+                        generate_fmt_body(variant_ident, &variant.fields, &debug_fields, &formatter)
+                    }
+                };
+                if variant_attrs.fmt.is_none() {
+                    all_fields.extend(debug_fields);
+                }
+                Ok(quote! { #pattern => { #body } })
+            })
+            .collect::<syn::Result<Vec<_>>>()?,
+        syn::Data::Union(_) => {
+            let (body, debug_fields) =
+                generate_union_body(&original_ident, &union_fields, &container_attrs, &formatter)?;
+            inferred_bounds.extend(infer_fields_bounds(&parsed.generics, &debug_fields));
+            all_fields.extend(debug_fields);
+            vec![quote! { _ => { #body } }]
         }
     };
 
-    let expanded = quote! {
-        #debug_impl {
-            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-                f.debug_struct(stringify!(#original_ident))
-                    #(#field_calls)*
-                    .finish()
+    let visit_fields = match container_attrs.visit_fields {
+        Some(_) => generate_visit_fields(&parsed, &container_attrs)?,
+        None => quote! {},
+    };
+
+    // 構造体に debug(bound = "...") が指定された場合は推論を行わずにそのまま利用し、
+    // フィールドに指定された場合はそのフィールドから推論される境界のみを置き換える
+    let bounds = container_attrs.bound.unwrap_or(inferred_bounds);
+    let generics = bound::add_bounds(parsed.generics.clone(), bounds);
+
+    let wrappers = generate_wrappers(all_fields.iter().map(|f| &f.attrs));
+    let cycle_guard = match container_attrs.cycle_safe {
+        Some(_) => generate_cycle_guard(&formatter),
+        None => quote! {},
+    };
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics std::fmt::Debug for #original_ident #ty_generics #where_clause {
+            fn fmt(&self, #formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                #wrappers
+                #cycle_guard
+
+                match *self {
+                    #(#arms)*
+                }
             }
         }
-    };
 
-    expanded.into()
+        #visit_fields
+    })
 }
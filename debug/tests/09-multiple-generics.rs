@@ -0,0 +1,45 @@
+// The generated impl has to carry over every generic parameter of the input,
+// including lifetimes and const generics, together with the bounds and the
+// where-clause written by the caller. On top of those, the type parameters used
+// by the fields get a Debug bound.
+//
+//     impl<'a, A, B: Clone, const N: usize> Debug for Pair<'a, A, B, N>
+//     where
+//         A: Copy,
+//         A: Debug,
+//         B: Debug,
+//     {...}
+//
+//
+// Resources:
+//
+//   - A helper for placing generics into an impl signature:
+//     https://docs.rs/syn/2.0/syn/struct.Generics.html#method.split_for_impl
+
+use derive_debug::CustomDebug;
+
+#[derive(CustomDebug)]
+pub struct Pair<'a, A, B: Clone, const N: usize>
+where
+    A: Copy,
+{
+    name: &'a str,
+    first: A,
+    second: B,
+    #[debug = "{:x?}"]
+    values: [u8; N],
+}
+
+fn main() {
+    let pair = Pair {
+        name: "pair",
+        first: 1,
+        second: "two",
+        values: [10, 11],
+    };
+
+    let debug = format!("{:?}", pair);
+    let expected = r#"Pair { name: "pair", first: 1, second: "two", values: [a, b] }"#;
+
+    assert_eq!(debug, expected);
+}
@@ -0,0 +1,12 @@
+// The string given to `debug(bound = "...")` is parsed as a list of where
+// predicates, and a syntax error in it is reported at the string itself.
+
+use derive_debug::CustomDebug;
+
+#[derive(CustomDebug)]
+#[debug(bound = "T Debug")]
+pub struct Field<T> {
+    value: T,
+}
+
+fn main() {}
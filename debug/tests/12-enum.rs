@@ -0,0 +1,70 @@
+// CustomDebug can also be derived for enums. Unit, tuple and struct variants
+// are printed exactly the same way as #[derive(Debug)] would print them, using
+// debug_tuple and debug_struct for each variant, and #[debug = "..."] is
+// honoured on the fields of the variants.
+//
+//     impl<T: Debug> Debug for Message<T> {
+//         fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+//             match *self {
+//                 Self::Quit => f.write_str("Quit"),
+//                 Self::Write(ref __self_0) => f.debug_tuple("Write").field(__self_0).finish(),
+//                 ...
+//             }
+//         }
+//     }
+
+use derive_debug::CustomDebug;
+
+#[derive(CustomDebug)]
+pub enum Message<T> {
+    Quit,
+    Write(T),
+    Move {
+        x: i32,
+        y: i32,
+    },
+    Color(#[debug = "0x{:02x}"] u8, #[debug = "0x{:02x}"] u8, u8),
+    Flags {
+        #[debug = "0b{:08b}"]
+        bitmask: u8,
+    },
+}
+
+#[derive(Debug)]
+pub enum Expected<T> {
+    Quit,
+    Write(T),
+    Move { x: i32, y: i32 },
+}
+
+#[derive(CustomDebug)]
+pub enum Never {}
+
+fn main() {
+    assert_eq!(format!("{:?}", Message::<()>::Quit), format!("{:?}", Expected::<()>::Quit));
+    assert_eq!(
+        format!("{:?}", Message::Write("hello")),
+        format!("{:?}", Expected::Write("hello")),
+    );
+    assert_eq!(
+        format!("{:#?}", Message::Write("hello")),
+        format!("{:#?}", Expected::Write("hello")),
+    );
+    assert_eq!(
+        format!("{:?}", Message::<()>::Move { x: 1, y: 2 }),
+        format!("{:?}", Expected::<()>::Move { x: 1, y: 2 }),
+    );
+    assert_eq!(
+        format!("{:#?}", Message::<()>::Move { x: 1, y: 2 }),
+        format!("{:#?}", Expected::<()>::Move { x: 1, y: 2 }),
+    );
+
+    assert_eq!(
+        format!("{:?}", Message::<()>::Color(255, 10, 0)),
+        "Color(0xff, 0x0a, 0)",
+    );
+    assert_eq!(
+        format!("{:?}", Message::<()>::Flags { bitmask: 0b00011100 }),
+        "Flags { bitmask: 0b00011100 }",
+    );
+}
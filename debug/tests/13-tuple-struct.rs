@@ -0,0 +1,41 @@
+// Tuple structs and unit structs are printed in the same format as
+// #[derive(Debug)], and #[debug = "..."] works on positional fields as well.
+//
+// With #[debug(transparent)] a struct with exactly one field is printed as
+// just its inner value, which is convenient for newtypes.
+
+use derive_debug::CustomDebug;
+
+#[derive(CustomDebug)]
+pub struct Point(i32, #[debug = "{:+}"] i32);
+
+#[derive(CustomDebug)]
+pub struct Unit;
+
+#[derive(CustomDebug)]
+pub struct Id(u64);
+
+#[derive(CustomDebug)]
+#[debug(transparent)]
+pub struct UserId(u64);
+
+#[derive(CustomDebug)]
+#[debug(transparent)]
+pub struct Name<'a> {
+    value: &'a str,
+}
+
+#[derive(CustomDebug)]
+#[debug(transparent)]
+pub struct Mask(#[debug = "0b{:04b}"] u8);
+
+fn main() {
+    assert_eq!(format!("{:?}", Point(1, 2)), "Point(1, +2)");
+    assert_eq!(format!("{:#?}", Point(1, 2)), "Point(\n    1,\n    +2,\n)");
+    assert_eq!(format!("{:?}", Unit), "Unit");
+    assert_eq!(format!("{:?}", Id(7)), "Id(7)");
+
+    assert_eq!(format!("{:?}", UserId(7)), "7");
+    assert_eq!(format!("{:?}", Name { value: "name" }), r#""name""#);
+    assert_eq!(format!("{:?}", Mask(5)), "0b0101");
+}
@@ -0,0 +1,29 @@
+// Attributes which do not belong to CustomDebug, such as doc comments, lint
+// attributes or the attributes of other derive macros, are ignored by the
+// derive instead of being rejected.
+
+use derive_debug::CustomDebug;
+
+/// A field with documentation.
+#[derive(CustomDebug)]
+#[allow(dead_code)]
+pub struct Field {
+    /// The name of the field.
+    #[allow(clippy::all)]
+    name: &'static str,
+    #[rustfmt::skip]
+    #[debug = "0b{:08b}"]
+    bitmask: u8,
+}
+
+fn main() {
+    let f = Field {
+        name: "F",
+        bitmask: 0b00011100,
+    };
+
+    let debug = format!("{:?}", f);
+    let expected = r#"Field { name: "F", bitmask: 0b00011100 }"#;
+
+    assert_eq!(debug, expected);
+}
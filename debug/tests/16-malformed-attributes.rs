@@ -0,0 +1,43 @@
+// Only malformed debug attributes are reported, each at the precise location
+// of the mistake.
+
+use derive_debug::CustomDebug;
+
+#[derive(CustomDebug)]
+pub struct NotString {
+    #[debug = 8]
+    bitmask: u8,
+}
+
+#[derive(CustomDebug)]
+pub struct MissingValue {
+    #[debug]
+    bitmask: u8,
+}
+
+#[derive(CustomDebug)]
+pub struct Duplicate {
+    #[debug = "0b{:08b}"]
+    #[debug = "0x{:02x}"]
+    bitmask: u8,
+}
+
+#[derive(CustomDebug)]
+pub struct Unknown {
+    #[debug(unknown)]
+    bitmask: u8,
+}
+
+#[derive(CustomDebug)]
+pub struct InvalidExpr {
+    #[debug(expr = "bitmask.count_ones(")]
+    bitmask: u8,
+}
+
+#[derive(CustomDebug)]
+pub struct InvalidWidth {
+    #[debug(hex(width))]
+    bitmask: u8,
+}
+
+fn main() {}
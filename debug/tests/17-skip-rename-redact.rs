@@ -0,0 +1,65 @@
+// Fields can be left out of the output with #[debug(skip)]. The output then
+// ends with `..` via finish_non_exhaustive() so that readers know something is
+// hidden.
+//
+// #[debug(rename = "...")] changes the printed name of a field, and
+// #[debug(redact)] prints a fixed placeholder instead of the value, optionally
+// with the length of the value or a hash of it:
+//
+//     #[debug(redact)]         => [REDACTED]
+//     #[debug(redact(len))]    => [REDACTED; len=6]
+//     #[debug(redact(hash))]   => [REDACTED; hash=...]
+//
+// Skipped and redacted fields do not require their type to implement Debug.
+
+use derive_debug::CustomDebug;
+use std::fmt::Debug;
+
+struct NotDebug;
+
+#[derive(CustomDebug)]
+pub struct Credentials<C> {
+    #[debug(rename = "user")]
+    username: String,
+    #[debug(redact(len))]
+    password: String,
+    #[debug(redact)]
+    token: C,
+    #[debug(redact(hash))]
+    fingerprint: &'static str,
+    #[debug(skip)]
+    cache: Vec<NotDebug>,
+}
+
+#[derive(CustomDebug)]
+pub struct Handle(u32, #[debug(skip)] NotDebug);
+
+#[derive(CustomDebug)]
+pub enum Secret<T> {
+    Plain(#[debug(redact)] T),
+}
+
+fn assert_debug<F: Debug>() {}
+
+fn main() {
+    assert_debug::<Credentials<NotDebug>>();
+    assert_debug::<Secret<NotDebug>>();
+
+    let credentials = Credentials {
+        username: "ferris".to_owned(),
+        password: "hunter".to_owned(),
+        token: NotDebug,
+        fingerprint: "abc",
+        cache: vec![NotDebug],
+    };
+
+    let debug = format!("{:?}", credentials);
+    assert!(debug.starts_with(
+        r#"Credentials { user: "ferris", password: [REDACTED; len=6], token: [REDACTED], fingerprint: [REDACTED; hash="#
+    ));
+    assert!(debug.ends_with("], .. }"));
+    assert!(!debug.contains("abc"));
+
+    assert_eq!(format!("{:?}", Handle(1, NotDebug)), "Handle(1, ..)");
+    assert_eq!(format!("{:?}", Secret::Plain(NotDebug)), "Plain([REDACTED])");
+}
@@ -0,0 +1,40 @@
+// A field can only be formatted in one way, and a skipped field is not
+// formatted at all.
+
+use derive_debug::CustomDebug;
+
+#[derive(CustomDebug)]
+pub struct Redacted {
+    #[debug = "{:?}"]
+    #[debug(redact)]
+    password: String,
+}
+
+#[derive(CustomDebug)]
+pub struct Skipped {
+    #[debug(skip, redact)]
+    password: String,
+}
+
+#[derive(CustomDebug)]
+pub struct Renamed(#[debug(rename = "value")] u8);
+
+#[derive(CustomDebug)]
+pub struct Truncated {
+    #[debug(max_items = 2, max_len = 4)]
+    names: Vec<String>,
+}
+
+#[derive(CustomDebug)]
+pub struct RedactedAlternate {
+    #[debug(redact, alt = "{:#?}")]
+    password: String,
+}
+
+#[derive(CustomDebug)]
+pub struct HexBytes {
+    #[debug(hex, bytes)]
+    size: u64,
+}
+
+fn main() {}
@@ -0,0 +1,65 @@
+// #[debug(with = "path::to::function")] formats a field with a function of the
+// signature `fn(&T, &mut fmt::Formatter) -> fmt::Result`, for output that
+// cannot be expressed with a format string. `T` is exactly the type of the
+// field, so generic functions are usually the most convenient.
+//
+// The debug_helpers crate ships a few such functions for bytes in hex,
+// truncated collections and durations. The bounds required by the function
+// are not inferred, so generic fields may need `debug(bound = "...")`.
+
+use derive_debug::CustomDebug;
+use std::fmt;
+use std::time::Duration;
+
+mod fmt_utils {
+    use std::fmt;
+
+    pub fn upper<T: AsRef<str>>(value: &T, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", value.as_ref().to_uppercase())
+    }
+}
+
+fn count<T>(value: &Vec<T>, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "<{} items>", value.len())
+}
+
+#[derive(CustomDebug)]
+pub struct Request<T> {
+    #[debug(with = "fmt_utils::upper")]
+    method: &'static str,
+    #[debug(with = "debug_helpers::hex::truncated::<4, _>")]
+    body: Vec<u8>,
+    #[debug(with = "debug_helpers::hex::bytes")]
+    checksum: [u8; 2],
+    #[debug(with = "debug_helpers::truncated::items::<2, _, _>")]
+    headers: Vec<&'static str>,
+    #[debug(with = "debug_helpers::truncated::chars::<5, _>")]
+    path: String,
+    #[debug(with = "debug_helpers::duration::human")]
+    elapsed: Duration,
+    #[debug(with = "count")]
+    values: Vec<T>,
+}
+
+fn main() {
+    struct NotDebug;
+
+    let request = Request {
+        method: "get",
+        body: vec![0xde, 0xad, 0xbe, 0xef, 0x00, 0x01],
+        checksum: [0xab, 0xcd],
+        headers: vec!["accept", "host", "user-agent"],
+        path: "/index.html".to_owned(),
+        elapsed: Duration::from_millis(62_500),
+        values: vec![NotDebug, NotDebug],
+    };
+
+    let debug = format!("{:?}", request);
+    let expected = concat!(
+        r#"Request { method: GET, body: 0xdeadbeef... (2 more), checksum: 0xabcd, "#,
+        r#"headers: ["accept", "host", ... (1 more)], path: "/inde"... (6 more), "#,
+        r#"elapsed: 1m 2.5s, values: <2 items> }"#,
+    );
+
+    assert_eq!(debug, expected);
+}
@@ -0,0 +1,57 @@
+// The format string of #[debug = "..."] is checked by the derive itself, so
+// that a typo is reported on the attribute instead of somewhere inside the
+// generated code.
+//
+// The string must contain exactly one placeholder, which refers to the field,
+// and the placeholder must use a formatting trait known to std::fmt. Literal
+// braces are still written as `{{` and `}}`.
+
+use derive_debug::CustomDebug;
+
+#[derive(CustomDebug)]
+pub struct Valid {
+    #[debug = "{{0b{:08b}}}"]
+    bits: u8,
+    #[debug = "{0:>+#010.3e}"]
+    float: f64,
+    #[debug = "{:*^8x?}"]
+    bytes: Vec<u8>,
+}
+
+#[derive(CustomDebug)]
+pub struct UnknownTrait {
+    #[debug = "0b{:08q}"]
+    bitmask: u8,
+}
+
+#[derive(CustomDebug)]
+pub struct TwoPlaceholders {
+    #[debug = "{} and {}"]
+    bitmask: u8,
+}
+
+#[derive(CustomDebug)]
+pub struct NoPlaceholder {
+    #[debug = "{{}}"]
+    bitmask: u8,
+}
+
+#[derive(CustomDebug)]
+pub struct Unterminated {
+    #[debug = "0b{:08b"]
+    bitmask: u8,
+}
+
+#[derive(CustomDebug)]
+pub struct NamedArgument {
+    #[debug = "{bitmask:08b}"]
+    bitmask: u8,
+}
+
+#[derive(CustomDebug)]
+pub struct WidthArgument {
+    #[debug = "{:width$}"]
+    bitmask: u8,
+}
+
+fn main() {}
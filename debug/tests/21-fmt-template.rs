@@ -0,0 +1,85 @@
+// #[debug(fmt = "...")] on a struct or an enum variant replaces the
+// debug_struct output with a compact one-line rendering. Placeholders refer to
+// named fields by name and to tuple fields by index, and can use any format
+// spec:
+//
+//     #[derive(CustomDebug)]
+//     #[debug(fmt = "Point({x}, {y})")]
+//     pub struct Point {
+//         x: i32,
+//         y: i32,
+//     }
+//
+// The referenced fields must implement the trait of their placeholder, i.e.
+// Display for `{x}` and Debug for `{x:?}`, and the inferred bounds follow
+// the placeholders accordingly. Fields left out of the template are not
+// printed, whatever debug attributes they have.
+
+use derive_debug::CustomDebug;
+
+#[derive(CustomDebug)]
+#[debug(fmt = "Point({x}, {y})")]
+pub struct Point {
+    x: i32,
+    y: i32,
+}
+
+#[derive(CustomDebug)]
+#[debug(fmt = "#{0:02x}{1:02x}{2:02x}")]
+pub struct Rgb(u8, u8, u8);
+
+#[derive(CustomDebug)]
+#[debug(fmt = "{type} {{ {value:?} }}")]
+pub struct Wrapper<'a, T> {
+    r#type: &'static str,
+    value: &'a T,
+}
+
+#[derive(CustomDebug)]
+#[debug(fmt = "User({name})")]
+pub struct User {
+    name: String,
+    #[debug(redact)]
+    #[allow(dead_code)]
+    password: String,
+}
+
+#[derive(CustomDebug)]
+pub enum Shape {
+    #[debug(fmt = "Circle(r={radius:.1})")]
+    Circle { radius: f64 },
+    #[debug(fmt = "Rect({0}x{1})")]
+    Rect(u32, u32),
+    #[debug(fmt = "<empty>")]
+    Empty,
+    Line { length: u32 },
+}
+
+fn main() {
+    assert_eq!(format!("{:?}", Point { x: 1, y: -2 }), "Point(1, -2)");
+    assert_eq!(format!("{:?}", Rgb(255, 128, 0)), "#ff8000");
+
+    struct NotDisplay;
+    impl std::fmt::Debug for NotDisplay {
+        fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+            f.write_str("NotDisplay")
+        }
+    }
+    let value = NotDisplay;
+    let wrapper = Wrapper {
+        r#type: "Wrapper",
+        value: &value,
+    };
+    assert_eq!(format!("{:?}", wrapper), "Wrapper { NotDisplay }");
+
+    let user = User {
+        name: "alice".to_owned(),
+        password: "hunter2".to_owned(),
+    };
+    assert_eq!(format!("{:?}", user), "User(alice)");
+
+    assert_eq!(format!("{:?}", Shape::Circle { radius: 1.25 }), "Circle(r=1.2)");
+    assert_eq!(format!("{:?}", Shape::Rect(3, 4)), "Rect(3x4)");
+    assert_eq!(format!("{:?}", Shape::Empty), "<empty>");
+    assert_eq!(format!("{:?}", Shape::Line { length: 5 }), "Line { length: 5 }");
+}
@@ -0,0 +1,54 @@
+// Placeholders of #[debug(fmt = "...")] are checked against the fields of the
+// struct or variant when the derive is expanded.
+//
+// The template passes the fields to format_args! as they are, so a field with
+// its own formatting attribute such as redact cannot be referenced; otherwise
+// the redacted value would silently be printed.
+
+use derive_debug::CustomDebug;
+
+#[derive(CustomDebug)]
+#[debug(fmt = "Point({x}, {z})")]
+pub struct UnknownField {
+    x: i32,
+    y: i32,
+}
+
+#[derive(CustomDebug)]
+#[debug(fmt = "Pair({0}, {2})")]
+pub struct UnknownIndex(i32, i32);
+
+#[derive(CustomDebug)]
+#[debug(fmt = "Point({}, {})")]
+pub struct Positional {
+    x: i32,
+    y: i32,
+}
+
+#[derive(CustomDebug)]
+pub enum Shape {
+    #[debug(fmt = "Circle({radius})")]
+    Circle { r: f64 },
+}
+
+#[derive(CustomDebug)]
+#[debug(fmt = "Shape")]
+pub enum OnEnum {
+    Circle,
+}
+
+#[derive(CustomDebug)]
+#[debug(fmt = "User({name}, {password})")]
+pub struct Redacted {
+    name: String,
+    #[debug(redact)]
+    password: String,
+}
+
+#[derive(CustomDebug)]
+pub enum Event {
+    #[debug(fmt = "Resize({0})")]
+    Resize(#[debug(hex)] u32),
+}
+
+fn main() {}
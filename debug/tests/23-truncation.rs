@@ -0,0 +1,82 @@
+// Large collections and strings flood the logs when printed in full. With
+// #[debug(max_items = N)] only the first N items of a field are printed, and
+// with #[debug(max_len = N)] only the first N characters of a string:
+//
+//     Request { body: [1, 2, 3, ... (997 more)], path: "/inde"... (6 more) }
+//
+// A map such as `HashMap<K, V>` or `BTreeMap<K, V>` keeps the `{k: v}` form,
+// ending with `..` when entries are left out.
+//
+// Given on the struct, the limits become the default of the fields whose type
+// is a sequence like `Vec<T>`, `VecDeque<T>`, `[T; N]` or `&[T]` for max_items,
+// and `String` or `&str` for max_len. Fields with their own debug attribute
+// are left as they are.
+
+use derive_debug::CustomDebug;
+use std::collections::{BTreeMap, VecDeque};
+
+#[derive(CustomDebug)]
+pub struct Request<'a, T> {
+    #[debug(max_items = 3)]
+    body: Vec<u8>,
+    #[debug(max_len = 5)]
+    path: String,
+    #[debug(max_items = 1)]
+    headers: BTreeMap<&'static str, &'static str>,
+    #[debug(max_items = 2)]
+    cookies: &'a BTreeMap<&'static str, u32>,
+    #[debug(max_items = 2)]
+    values: &'a [T],
+}
+
+#[derive(CustomDebug)]
+#[debug(max_items = 2, max_len = 4)]
+pub struct Defaults<'a> {
+    list: Vec<u32>,
+    queue: VecDeque<u32>,
+    array: [u32; 3],
+    slice: &'a [u32],
+    name: String,
+    label: &'a str,
+    #[debug = "{:?}"]
+    full: Vec<u32>,
+    count: u32,
+}
+
+fn main() {
+    let values = [1.5, 2.5];
+    let cookies = BTreeMap::from([("session", 1)]);
+    let request = Request {
+        body: (1..=1000).map(|i| i as u8).collect(),
+        path: "/index.html".to_owned(),
+        headers: BTreeMap::from([("accept", "*/*"), ("host", "localhost")]),
+        cookies: &cookies,
+        values: &values,
+    };
+    assert_eq!(
+        format!("{:?}", request),
+        concat!(
+            r#"Request { body: [1, 2, 3, ... (997 more)], path: "/inde"... (6 more), "#,
+            r#"headers: {"accept": "*/*", ..}, cookies: {"session": 1}, values: [1.5, 2.5] }"#,
+        ),
+    );
+
+    let defaults = Defaults {
+        list: vec![1, 2, 3],
+        queue: VecDeque::from([4, 5, 6, 7]),
+        array: [8, 9, 10],
+        slice: &[11],
+        name: "abcdef".to_owned(),
+        label: "ghi",
+        full: vec![12, 13, 14],
+        count: 15,
+    };
+    assert_eq!(
+        format!("{:?}", defaults),
+        concat!(
+            "Defaults { list: [1, 2, ... (1 more)], queue: [4, 5, ... (2 more)], ",
+            "array: [8, 9, ... (1 more)], slice: [11], ",
+            r#"name: "abcd"... (2 more), label: "ghi", full: [12, 13, 14], count: 15 }"#,
+        ),
+    );
+}
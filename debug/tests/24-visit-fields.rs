@@ -0,0 +1,103 @@
+// Structured logging backends want the fields of a value as key/value pairs
+// rather than a single string. With #[debug(visit_fields)] the derive also
+// implements debug_helpers::visit::VisitFields, which passes every field to a
+// visitor:
+//
+//     impl VisitFields for Request {
+//         fn visit_fields(&self, visitor: &mut dyn FieldVisitor) {
+//             match *self {
+//                 Self { ref method, ref token, .. } => {
+//                     visitor.visit_field("method", method);
+//                     visitor.visit_field("token", &format_args!("[REDACTED]"));
+//                 }
+//             }
+//         }
+//     }
+//
+// The fields are passed the same way as they are printed by Debug, honouring
+// skip, rename, redact and the formatting attributes.
+
+use debug_helpers::visit::{FieldVisitor, VisitFields};
+use derive_debug::CustomDebug;
+use std::fmt;
+
+#[derive(CustomDebug)]
+#[debug(visit_fields)]
+pub struct Request<T> {
+    #[debug(rename = "verb")]
+    method: &'static str,
+    #[debug(redact)]
+    token: String,
+    #[debug = "0x{:04x}"]
+    flags: u16,
+    #[debug(skip)]
+    cache: Vec<u8>,
+    #[debug(max_items = 2)]
+    values: Vec<T>,
+}
+
+#[derive(CustomDebug)]
+#[debug(visit_fields, fmt = "Point({x}, {y})")]
+pub struct Point {
+    x: i32,
+    y: i32,
+}
+
+#[derive(CustomDebug)]
+#[debug(visit_fields)]
+pub enum Event {
+    Click(u32, u32),
+    Key { code: char },
+    Quit,
+}
+
+#[derive(Default)]
+struct Collect(Vec<(String, String)>);
+
+impl FieldVisitor for Collect {
+    fn visit_field(&mut self, name: &str, value: &dyn fmt::Debug) {
+        self.0.push((name.to_owned(), format!("{:?}", value)));
+    }
+}
+
+fn collect(value: &dyn VisitFields) -> Vec<(String, String)> {
+    let mut visitor = Collect::default();
+    value.visit_fields(&mut visitor);
+    visitor.0
+}
+
+fn pairs(expected: &[(&str, &str)]) -> Vec<(String, String)> {
+    expected
+        .iter()
+        .map(|(name, value)| (name.to_string(), value.to_string()))
+        .collect()
+}
+
+fn main() {
+    let request = Request {
+        method: "GET",
+        token: "secret".to_owned(),
+        flags: 10,
+        cache: vec![],
+        values: vec![1, 2, 3],
+    };
+    assert_eq!(
+        collect(&request),
+        pairs(&[
+            ("verb", r#""GET""#),
+            ("token", "[REDACTED]"),
+            ("flags", "0x000a"),
+            ("values", "[1, 2, ... (1 more)]"),
+        ]),
+    );
+
+    assert_eq!(collect(&Point { x: 1, y: 2 }), pairs(&[("x", "1"), ("y", "2")]));
+
+    assert_eq!(collect(&Event::Click(3, 4)), pairs(&[("0", "3"), ("1", "4")]));
+    assert_eq!(collect(&Event::Key { code: 'q' }), pairs(&[("code", "'q'")]));
+    assert!(collect(&Event::Quit).is_empty());
+
+    let mut names = vec![];
+    request.visit_fields(&mut |name: &str, _: &dyn fmt::Debug| names.push(name.to_owned()));
+    assert_eq!(names, ["verb", "token", "flags", "values"]);
+}
@@ -0,0 +1,81 @@
+// In pretty mode ({:#?}) a field formatted with #[debug = "..."] is printed
+// with the `#` flag added to its Debug placeholder, so that nested values are
+// indented the same way as #[derive(Debug)] would indent them. Placeholders of
+// other traits are left as they are, since `#` changes their output.
+//
+// #[debug(alt = "...")] gives a separate format string which is used only in
+// pretty mode, for a field with or without #[debug = "..."].
+
+use derive_debug::CustomDebug;
+
+#[derive(Debug)]
+pub struct Inner {
+    name: &'static str,
+    values: Vec<u8>,
+}
+
+#[derive(CustomDebug)]
+pub struct Custom {
+    #[debug = "{:?}"]
+    inner: Inner,
+    #[debug = "0x{:02x}"]
+    flags: u8,
+}
+
+#[derive(Debug)]
+pub struct Derived {
+    inner: Inner,
+    flags: Hex,
+}
+
+pub struct Hex(u8);
+
+impl std::fmt::Debug for Hex {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "0x{:02x}", self.0)
+    }
+}
+
+#[derive(CustomDebug)]
+pub struct Alternate {
+    #[debug = "{}ms"]
+    #[debug(alt = "{} milliseconds")]
+    elapsed: u64,
+    #[debug(alt = "0b{:08b}")]
+    bits: u8,
+}
+
+fn main() {
+    let custom = Custom {
+        inner: Inner {
+            name: "F",
+            values: vec![1, 2],
+        },
+        flags: 10,
+    };
+    let derived = Derived {
+        inner: Inner {
+            name: "F",
+            values: vec![1, 2],
+        },
+        flags: Hex(10),
+    };
+    assert_eq!(
+        format!("{:#?}", custom),
+        format!("{:#?}", derived).replace("Derived", "Custom"),
+    );
+    assert_eq!(
+        format!("{:?}", custom),
+        format!("{:?}", derived).replace("Derived", "Custom"),
+    );
+
+    let alternate = Alternate {
+        elapsed: 15,
+        bits: 0b00011100,
+    };
+    assert_eq!(format!("{:?}", alternate), "Alternate { elapsed: 15ms, bits: 28 }");
+    assert_eq!(
+        format!("{:#?}", alternate),
+        "Alternate {\n    elapsed: 15 milliseconds,\n    bits: 0b00011100,\n}",
+    );
+}
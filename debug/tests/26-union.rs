@@ -0,0 +1,77 @@
+// Unions cannot derive Debug, since which field is valid is not known. For a
+// union CustomDebug requires one of two attributes:
+//
+//     #[debug(unsafe(union_as = "field"))]  => Value { bits: 1065353216, .. }
+//     #[debug(opaque)]                      => Value { <4 bytes> }
+//
+// union_as reads the chosen field whatever field was written last, so it has
+// to be wrapped in unsafe(...): the attribute asserts that the field is valid
+// for every value of the union, like the integer `bits` below. See the Safety
+// section of the CustomDebug docs. Use opaque when no such field exists. The
+// other fields are left out of the output, and the chosen field honours its
+// own debug attributes.
+//
+// FFI structs with #[repr(C)] can then derive CustomDebug as usual even when
+// they contain unions.
+
+use derive_debug::CustomDebug;
+
+#[derive(CustomDebug, Clone, Copy)]
+#[repr(C)]
+#[debug(unsafe(union_as = "bits"))]
+pub union Value {
+    float: f32,
+    #[debug = "0x{:08x}"]
+    bits: u32,
+}
+
+#[derive(CustomDebug, Clone, Copy)]
+#[repr(C)]
+#[debug(opaque)]
+pub union Pointer {
+    address: usize,
+    pointer: *const u8,
+}
+
+#[derive(CustomDebug)]
+#[repr(C)]
+#[debug(unsafe(union_as = "raw"))]
+pub union Generic<T: Copy> {
+    raw: T,
+    bytes: [u8; 8],
+}
+
+#[derive(CustomDebug)]
+#[repr(C)]
+pub struct Tagged {
+    tag: u8,
+    value: Value,
+    pointer: Pointer,
+}
+
+fn main() {
+    let value = Value { float: 1.0 };
+    assert_eq!(format!("{:?}", value), "Value { bits: 0x3f800000, .. }");
+
+    let pointer = Pointer { address: 0 };
+    assert_eq!(
+        format!("{:?}", pointer),
+        format!("Pointer {{ <{} bytes> }}", std::mem::size_of::<usize>()),
+    );
+
+    let generic = Generic { raw: 7u64 };
+    assert_eq!(format!("{:?}", generic), "Generic { raw: 7, .. }");
+
+    let tagged = Tagged {
+        tag: 1,
+        value,
+        pointer,
+    };
+    assert_eq!(
+        format!("{:?}", tagged),
+        format!(
+            "Tagged {{ tag: 1, value: Value {{ bits: 0x3f800000, .. }}, pointer: Pointer {{ <{} bytes> }} }}",
+            std::mem::size_of::<usize>(),
+        ),
+    );
+}
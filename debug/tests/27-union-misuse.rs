@@ -0,0 +1,45 @@
+// A union needs to say how it is printed, and the attributes which only make
+// sense for one kind of item are rejected on the others.
+
+use derive_debug::CustomDebug;
+
+#[derive(CustomDebug)]
+pub union Missing {
+    float: f32,
+    bits: u32,
+}
+
+#[derive(CustomDebug)]
+#[debug(unsafe(union_as = "int"))]
+pub union UnknownField {
+    float: f32,
+    bits: u32,
+}
+
+#[derive(CustomDebug)]
+#[debug(unsafe(union_as = "bits"), opaque)]
+pub union Both {
+    float: f32,
+    bits: u32,
+}
+
+#[derive(CustomDebug)]
+#[debug(union_as = "bits")]
+pub union NotUnsafe {
+    float: f32,
+    bits: u32,
+}
+
+#[derive(CustomDebug)]
+#[debug(opaque, transparent)]
+pub union Transparent {
+    bits: u32,
+}
+
+#[derive(CustomDebug)]
+#[debug(opaque)]
+pub struct OpaqueStruct {
+    bits: u32,
+}
+
+fn main() {}
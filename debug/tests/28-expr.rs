@@ -0,0 +1,58 @@
+// #[debug(expr = "...")] prints the value of an expression in place of the
+// field. The expression can refer to the field and the other fields of the
+// struct or variant by name, as references, and to `self`. Tuple fields are
+// bound as `__self_0`, `__self_1` and so on:
+//
+//     Buffer { buf: 1024 bytes, capacity: 4096, usage: 25 }
+//
+// The expression must evaluate to something implementing Debug. Bounds are
+// not inferred for such fields, so generic fields may need
+// `debug(bound = "...")`.
+
+#![deny(warnings)]
+
+use derive_debug::CustomDebug;
+
+#[derive(CustomDebug)]
+pub struct Buffer {
+    #[debug(expr = "format_args!(\"{} bytes\", buf.len())")]
+    buf: Vec<u8>,
+    capacity: usize,
+    #[debug(expr = "buf.len() * 100 / self.capacity")]
+    usage: (),
+}
+
+#[derive(CustomDebug)]
+pub struct Pair<T>(#[debug(expr = "self.0.len()")] Vec<T>, &'static str);
+
+#[derive(CustomDebug)]
+pub enum Message {
+    Text {
+        #[debug(expr = "text.chars().count()")]
+        text: String,
+    },
+    Binary(#[debug(expr = "format_args!(\"<{} bytes>\", __self_0.len())")] Vec<u8>),
+}
+
+fn main() {
+    struct NotDebug;
+
+    let buffer = Buffer {
+        buf: vec![0; 1024],
+        capacity: 4096,
+        usage: (),
+    };
+    assert_eq!(
+        format!("{:?}", buffer),
+        "Buffer { buf: 1024 bytes, capacity: 4096, usage: 25 }",
+    );
+
+    let pair = Pair(vec![NotDebug, NotDebug], "pair");
+    assert_eq!(format!("{:?}", pair), r#"Pair(2, "pair")"#);
+
+    let text = Message::Text {
+        text: "héllo".to_owned(),
+    };
+    assert_eq!(format!("{:?}", text), "Text { text: 5 }");
+    assert_eq!(format!("{:?}", Message::Binary(vec![1, 2])), "Binary(<2 bytes>)");
+}
@@ -0,0 +1,73 @@
+// Types holding back-references like Rc<RefCell<Node>> recurse forever when
+// printed with a plain Debug impl. With #[debug(cycle_safe)] the address of
+// the value being formatted is recorded in a thread local set, and reaching
+// the same value again from one of its own fields prints `<cycle>` instead:
+//
+//     Node { name: "a", next: Some(RefCell { value: Node { name: "b", next: Some(RefCell { value: <cycle> }) } }) }
+//
+// Only values on the current path are tracked, so a value shared by two
+// fields which does not point back to itself is printed in full both times.
+// The type is recorded together with the address, since the first field of a
+// value lives at the same address as the value itself.
+// The address is removed once its output is finished, even when formatting
+// panics.
+
+#![deny(warnings)]
+
+use derive_debug::CustomDebug;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+#[derive(CustomDebug)]
+#[debug(cycle_safe)]
+pub struct Node {
+    name: &'static str,
+    next: Option<Rc<RefCell<Node>>>,
+}
+
+#[derive(CustomDebug)]
+#[debug(cycle_safe)]
+pub enum Tree {
+    Leaf(u8),
+    Branch(Rc<Tree>, Rc<Tree>),
+}
+
+#[derive(CustomDebug)]
+#[debug(cycle_safe)]
+pub struct Wrap<T> {
+    inner: T,
+}
+
+fn main() {
+    let a = Rc::new(RefCell::new(Node {
+        name: "a",
+        next: None,
+    }));
+    let b = Rc::new(RefCell::new(Node {
+        name: "b",
+        next: Some(a.clone()),
+    }));
+    a.borrow_mut().next = Some(b.clone());
+
+    let expected = concat!(
+        r#"Node { name: "a", next: Some(RefCell { value: Node { name: "b", "#,
+        r#"next: Some(RefCell { value: <cycle> }) } }) }"#,
+    );
+    assert_eq!(format!("{:?}", a.borrow()), expected);
+    // The addresses are forgotten once printed, so printing again gives the same output.
+    assert_eq!(format!("{:?}", a.borrow()), expected);
+    assert!(format!("{:#?}", b.borrow()).contains("<cycle>"));
+
+    // Break the cycle so that the nodes are dropped.
+    a.borrow_mut().next = None;
+
+    let leaf = Rc::new(Tree::Leaf(1));
+    let tree = Tree::Branch(leaf.clone(), leaf);
+    assert_eq!(format!("{:?}", tree), "Branch(Leaf(1), Leaf(1))");
+
+    // The inner Wrap is at the same address as the outer one, but is a different type.
+    let nested = Wrap {
+        inner: Wrap { inner: 1u8 },
+    };
+    assert_eq!(format!("{:?}", nested), "Wrap { inner: Wrap { inner: 1 } }");
+}
@@ -0,0 +1,68 @@
+// Hex, binary and octal are the most common custom formats of a number, and
+// writing #[debug = "0x{:08x}"] over and over is easy to get wrong. The
+// shorthands #[debug(hex)], #[debug(bin)] and #[debug(oct)] generate that
+// format string for you, zero-padded to the width given in parentheses:
+//
+//     #[debug(hex)]     0xff
+//     #[debug(hex(8))]  0x000000ff
+//     #[debug(bin(4))]  0b0101
+//     #[debug(oct)]     0o755
+//
+// #[debug(bytes)] prints an unsigned integer as a human readable size in
+// binary units such as `512 B`, `1.5 KiB` or `2.0 GiB`.
+//
+// As with #[debug = "..."], the bounds of a generic field are inferred from
+// the formatting trait, i.e. LowerHex for hex.
+
+use derive_debug::CustomDebug;
+
+#[derive(CustomDebug)]
+pub struct Register {
+    #[debug(hex)]
+    value: u32,
+    #[debug(hex(8))]
+    address: u32,
+    #[debug(bin(4))]
+    flags: u8,
+    #[debug(oct)]
+    mode: u32,
+}
+
+#[derive(CustomDebug)]
+pub struct File {
+    #[debug(bytes)]
+    small: u64,
+    #[debug(bytes)]
+    medium: usize,
+    #[debug(bytes)]
+    large: u64,
+}
+
+#[derive(CustomDebug)]
+pub struct Wrapper<T>(#[debug(hex(4))] T);
+
+fn main() {
+    let register = Register {
+        value: 255,
+        address: 0xdead,
+        flags: 0b101,
+        mode: 0o755,
+    };
+    assert_eq!(
+        format!("{:?}", register),
+        "Register { value: 0xff, address: 0x0000dead, flags: 0b0101, mode: 0o755 }"
+    );
+
+    let file = File {
+        small: 512,
+        medium: 1536,
+        large: 2 * 1024 * 1024 * 1024,
+    };
+    assert_eq!(
+        format!("{:?}", file),
+        "File { small: 512 B, medium: 1.5 KiB, large: 2.0 GiB }"
+    );
+
+    // Only LowerHex is required from the type parameter.
+    assert_eq!(format!("{:?}", Wrapper(0xabu16)), "Wrapper(0x00ab)");
+}
@@ -0,0 +1,26 @@
+// #[debug(bytes)] converts the field to u64 before picking a unit, so it only
+// accepts the unsigned integer types which fit in u64. A reference, a wider
+// integer or a signed one would fail to compile inside the generated impl or
+// print a wrong size, and is rejected on the field type instead.
+
+use derive_debug::CustomDebug;
+
+#[derive(CustomDebug)]
+pub struct Reference<'a> {
+    #[debug(bytes)]
+    size: &'a u64,
+}
+
+#[derive(CustomDebug)]
+pub struct Wide {
+    #[debug(bytes)]
+    size: u128,
+}
+
+#[derive(CustomDebug)]
+pub struct Signed {
+    #[debug(bytes)]
+    size: i64,
+}
+
+fn main() {}
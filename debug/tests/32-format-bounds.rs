@@ -0,0 +1,60 @@
+// A generic field printed with #[debug = "..."] is bound by the trait of its
+// placeholder rather than by Debug, so `0x{:x}` requires only LowerHex and
+// `{}` only Display from the type parameter. When the field also has
+// #[debug(alt = "...")], the trait of that placeholder is required as well,
+// also next to Debug when the field has no #[debug = "..."].
+//
+// Id does not implement Debug, so it can be printed by Hex, Label and Both only
+// if no Debug bound is inferred for their fields.
+
+use derive_debug::CustomDebug;
+use std::fmt;
+
+pub struct Id(u32);
+
+impl fmt::LowerHex for Id {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::LowerHex::fmt(&self.0, f)
+    }
+}
+
+impl fmt::Display for Id {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "#{}", self.0)
+    }
+}
+
+#[derive(CustomDebug)]
+pub struct Hex<T> {
+    #[debug = "0x{:x}"]
+    value: T,
+}
+
+#[derive(CustomDebug)]
+pub struct Label<T>(#[debug = "<{}>"] T);
+
+#[derive(CustomDebug)]
+pub struct Both<T> {
+    #[debug = "{}"]
+    #[debug(alt = "0x{:08x}")]
+    value: T,
+}
+
+#[derive(CustomDebug)]
+pub struct AltOnly<T> {
+    #[debug(alt = "0x{:08x}")]
+    value: T,
+}
+
+fn main() {
+    assert_eq!(format!("{:?}", Hex { value: Id(255) }), "Hex { value: 0xff }");
+    assert_eq!(format!("{:?}", Label(Id(7))), "Label(<#7>)");
+
+    let both = Both { value: Id(42) };
+    assert_eq!(format!("{:?}", both), "Both { value: #42 }");
+    assert_eq!(format!("{:#?}", both), "Both {\n    value: 0x0000002a,\n}");
+
+    let alt_only = AltOnly { value: 42u32 };
+    assert_eq!(format!("{:?}", alt_only), "AltOnly { value: 42 }");
+    assert_eq!(format!("{:#?}", alt_only), "AltOnly {\n    value: 0x0000002a,\n}");
+}
@@ -5,8 +5,32 @@ fn tests() {
     t.pass("tests/02-impl-debug.rs");
     t.pass("tests/03-custom-format.rs");
     t.pass("tests/04-type-parameter.rs");
-    //t.pass("tests/05-phantom-data.rs");
-    //t.pass("tests/06-bound-trouble.rs");
-    //t.pass("tests/07-associated-type.rs");
-    //t.pass("tests/08-escape-hatch.rs");
+    t.pass("tests/05-phantom-data.rs");
+    t.pass("tests/06-bound-trouble.rs");
+    t.pass("tests/07-associated-type.rs");
+    t.pass("tests/08-escape-hatch.rs");
+    t.pass("tests/09-multiple-generics.rs");
+    t.pass("tests/10-field-bound.rs");
+    t.compile_fail("tests/11-invalid-bound.rs");
+    t.pass("tests/12-enum.rs");
+    t.pass("tests/13-tuple-struct.rs");
+    t.compile_fail("tests/14-transparent-multiple-fields.rs");
+    t.pass("tests/15-foreign-attributes.rs");
+    t.compile_fail("tests/16-malformed-attributes.rs");
+    t.pass("tests/17-skip-rename-redact.rs");
+    t.compile_fail("tests/18-conflicting-attributes.rs");
+    t.pass("tests/19-with.rs");
+    t.compile_fail("tests/20-invalid-format.rs");
+    t.pass("tests/21-fmt-template.rs");
+    t.compile_fail("tests/22-invalid-fmt-template.rs");
+    t.pass("tests/23-truncation.rs");
+    t.pass("tests/24-visit-fields.rs");
+    t.pass("tests/25-pretty-print.rs");
+    t.pass("tests/26-union.rs");
+    t.compile_fail("tests/27-union-misuse.rs");
+    t.pass("tests/28-expr.rs");
+    t.pass("tests/29-cycle-safe.rs");
+    t.pass("tests/30-number-formats.rs");
+    t.compile_fail("tests/31-invalid-bytes.rs");
+    t.pass("tests/32-format-bounds.rs");
 }
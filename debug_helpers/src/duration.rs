@@ -0,0 +1,74 @@
+//! Print durations in a compact human readable form.
+
+use std::fmt;
+use std::time::Duration;
+
+/// Print a duration such as `1h 2m 3.5s`, `250ms` or `12µs`.
+pub fn human(value: &Duration, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let secs = value.as_secs();
+    if secs == 0 {
+        return match value.subsec_nanos() {
+            nanos if nanos >= 1_000_000 => write!(f, "{}ms", nanos / 1_000_000),
+            nanos if nanos >= 1_000 => write!(f, "{}µs", nanos / 1_000),
+            nanos => write!(f, "{}ns", nanos),
+        };
+    }
+
+    let (hours, minutes, secs) = (secs / 3600, secs / 60 % 60, secs % 60);
+    if hours > 0 {
+        write!(f, "{}h ", hours)?;
+    }
+    if hours > 0 || minutes > 0 {
+        write!(f, "{}m ", minutes)?;
+    }
+
+    let millis = value.subsec_millis();
+    if millis == 0 {
+        write!(f, "{}s", secs)
+    } else {
+        let fraction = format!("{:03}", millis);
+        write!(f, "{}.{}s", secs, fraction.trim_end_matches('0'))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Human(Duration);
+
+    impl fmt::Debug for Human {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            human(&self.0, f)
+        }
+    }
+
+    fn format(value: Duration) -> String {
+        format!("{:?}", Human(value))
+    }
+
+    #[test]
+    fn sub_second_units() {
+        assert_eq!(format(Duration::from_nanos(0)), "0ns");
+        assert_eq!(format(Duration::from_nanos(999)), "999ns");
+        assert_eq!(format(Duration::from_nanos(12_345)), "12µs");
+        assert_eq!(format(Duration::from_millis(250)), "250ms");
+    }
+
+    #[test]
+    fn hours_minutes_and_seconds() {
+        assert_eq!(format(Duration::from_secs(5)), "5s");
+        assert_eq!(format(Duration::from_secs(62)), "1m 2s");
+        assert_eq!(format(Duration::from_secs(3723)), "1h 2m 3s");
+        // 分が 0 でも時間があれば省略しない
+        assert_eq!(format(Duration::from_secs(3605)), "1h 0m 5s");
+    }
+
+    #[test]
+    fn trailing_zeros_of_millis() {
+        assert_eq!(format(Duration::from_millis(3500)), "3.5s");
+        assert_eq!(format(Duration::from_millis(3050)), "3.05s");
+        assert_eq!(format(Duration::from_millis(3001)), "3.001s");
+        assert_eq!(format(Duration::from_millis(62_250)), "1m 2.25s");
+    }
+}
@@ -0,0 +1,69 @@
+//! Print bytes as a hex string such as `0xdeadbeef`.
+
+use std::fmt;
+
+/// Print all bytes as a lowercase hex string.
+pub fn bytes<T>(value: &T, f: &mut fmt::Formatter<'_>) -> fmt::Result
+where
+    T: AsRef<[u8]> + ?Sized,
+{
+    write_hex(value.as_ref(), f)
+}
+
+/// Print only the first `N` bytes followed by the number of omitted bytes.
+pub fn truncated<const N: usize, T>(value: &T, f: &mut fmt::Formatter<'_>) -> fmt::Result
+where
+    T: AsRef<[u8]> + ?Sized,
+{
+    let bytes = value.as_ref();
+    if bytes.len() <= N {
+        return write_hex(bytes, f);
+    }
+
+    write_hex(&bytes[..N], f)?;
+    write!(f, "... ({} more)", bytes.len() - N)
+}
+
+fn write_hex(bytes: &[u8], f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.write_str("0x")?;
+    for byte in bytes {
+        write!(f, "{:02x}", byte)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Hex<'a>(&'a [u8]);
+
+    impl fmt::Debug for Hex<'_> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            bytes(self.0, f)
+        }
+    }
+
+    struct Truncated<'a>(&'a [u8]);
+
+    impl fmt::Debug for Truncated<'_> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            truncated::<2, _>(self.0, f)
+        }
+    }
+
+    #[test]
+    fn all_bytes() {
+        assert_eq!(format!("{:?}", Hex(&[])), "0x");
+        assert_eq!(format!("{:?}", Hex(&[0xde, 0xad, 0x0b])), "0xdead0b");
+    }
+
+    #[test]
+    fn truncated_bytes() {
+        assert_eq!(format!("{:?}", Truncated(&[0x01, 0x02])), "0x0102");
+        assert_eq!(
+            format!("{:?}", Truncated(&[0x01, 0x02, 0x03, 0x04])),
+            "0x0102... (2 more)"
+        );
+    }
+}
@@ -0,0 +1,22 @@
+//! Runtime support for `derive_debug::CustomDebug`.
+//!
+//! The formatting functions in `duration`, `hex` and `truncated` are used with
+//! `#[debug(with = "...")]`. Every function has the signature
+//! `fn(&T, &mut fmt::Formatter) -> fmt::Result`.
+//!
+//! The traits in `visit` are implemented by `#[debug(visit_fields)]`.
+//!
+//! ```ignore
+//! #[derive(CustomDebug)]
+//! pub struct Request {
+//!     #[debug(with = "debug_helpers::hex::truncated::<16, _>")]
+//!     body: Vec<u8>,
+//!     #[debug(with = "debug_helpers::duration::human")]
+//!     elapsed: std::time::Duration,
+//! }
+//! ```
+
+pub mod duration;
+pub mod hex;
+pub mod truncated;
+pub mod visit;
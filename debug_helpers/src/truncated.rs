@@ -0,0 +1,77 @@
+//! Print only the first elements of a collection.
+
+use std::fmt;
+
+/// Print the first `N` items as a list, followed by `... (M more)` when the
+/// collection has more items.
+pub fn items<const N: usize, C, T>(value: &C, f: &mut fmt::Formatter<'_>) -> fmt::Result
+where
+    C: AsRef<[T]> + ?Sized,
+    T: fmt::Debug,
+{
+    let mut list = f.debug_list();
+    let mut iter = value.as_ref().iter();
+    list.entries(iter.by_ref().take(N));
+
+    let rest = iter.count();
+    if rest > 0 {
+        list.entry(&format_args!("... ({} more)", rest));
+    }
+    list.finish()
+}
+
+/// Print the first `N` characters of a string, followed by `... (M more)`
+/// when the string is longer.
+pub fn chars<const N: usize, T>(value: &T, f: &mut fmt::Formatter<'_>) -> fmt::Result
+where
+    T: AsRef<str> + ?Sized,
+{
+    let value = value.as_ref();
+    match value.char_indices().nth(N) {
+        Some((end, _)) => {
+            let rest = value[end..].chars().count();
+            write!(f, "{:?}... ({} more)", &value[..end], rest)
+        }
+        None => fmt::Debug::fmt(value, f),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Items<'a>(&'a [u32]);
+
+    impl fmt::Debug for Items<'_> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            items::<2, _, _>(self.0, f)
+        }
+    }
+
+    struct Chars<'a>(&'a str);
+
+    impl fmt::Debug for Chars<'_> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            chars::<3, _>(self.0, f)
+        }
+    }
+
+    #[test]
+    fn first_items() {
+        assert_eq!(format!("{:?}", Items(&[1, 2])), "[1, 2]");
+        assert_eq!(
+            format!("{:?}", Items(&[1, 2, 3, 4])),
+            "[1, 2, ... (2 more)]"
+        );
+    }
+
+    #[test]
+    fn first_chars() {
+        assert_eq!(format!("{:?}", Chars("abc")), r#""abc""#);
+        // バイト数ではなく文字数で数える
+        assert_eq!(
+            format!("{:?}", Chars("日本語です")),
+            r#""日本語"... (2 more)"#
+        );
+    }
+}
@@ -0,0 +1,32 @@
+//! Export the fields of a value one by one, e.g. as key/value pairs of a
+//! structured log.
+//!
+//! `VisitFields` is implemented by `#[derive(CustomDebug)]` when the struct or
+//! enum has `#[debug(visit_fields)]`. The fields are passed the same way as
+//! they are printed by Debug, so skipped fields are not visited and redacted
+//! fields are passed as their placeholder.
+
+use std::fmt;
+
+/// Receives the fields of a value
+pub trait FieldVisitor {
+    fn visit_field(&mut self, name: &str, value: &dyn fmt::Debug);
+}
+
+/// Any closure taking the name and the value of a field can be used as a visitor.
+impl<F> FieldVisitor for F
+where
+    F: FnMut(&str, &dyn fmt::Debug),
+{
+    fn visit_field(&mut self, name: &str, value: &dyn fmt::Debug) {
+        self(name, value)
+    }
+}
+
+/// A value whose fields can be visited one by one
+pub trait VisitFields {
+    /// Pass every field to the visitor. Fields of tuple structs and tuple
+    /// variants are named by their index, and for an enum only the fields of
+    /// the current variant are visited.
+    fn visit_fields(&self, visitor: &mut dyn FieldVisitor);
+}
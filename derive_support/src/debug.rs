@@ -0,0 +1,563 @@
+//! The field level `#[debug]` attributes of `derive_debug::CustomDebug`, which
+//! `#[builder(debug)]` of `derive_builder` applies to the builder as well.
+
+use proc_macro2::Span;
+use quote::{quote, ToTokens};
+use syn::{punctuated::Punctuated, spanned::Spanned, Token};
+
+use crate::{format, set_once};
+
+/// How the value of a field is printed instead of its own Debug impl
+pub enum FieldFormat {
+    /// #[debug = "..."], or the one generated by #[debug(hex)], #[debug(bin)] and #[debug(oct)]
+    Template(syn::LitStr),
+    /// #[debug(redact)]
+    Redact(Redaction),
+    /// #[debug(with = "path::to::fmt_fn")]
+    With(syn::ExprPath),
+    /// #[debug(expr = "...")]
+    Expr(syn::Expr),
+    /// #[debug(max_items = N)]
+    MaxItems(syn::LitInt),
+    /// #[debug(max_items = N)] on a map such as `HashMap<K, V>` or `BTreeMap<K, V>`
+    MaxEntries(syn::LitInt),
+    /// #[debug(max_len = N)]
+    MaxLen(syn::LitInt),
+    /// #[debug(bytes)]
+    Bytes,
+}
+
+/// The hint printed next to the placeholder of a redacted field
+pub enum Redaction {
+    /// #[debug(redact)]
+    Plain,
+    /// #[debug(redact(len))]
+    Len,
+    /// #[debug(redact(hash))]
+    Hash,
+}
+
+/// Values given by the field level #[debug = "..."] and #[debug(...)] attributes
+#[derive(Default)]
+pub struct FieldAttributes {
+    pub format: Option<FieldFormat>,
+    pub bound: Option<Vec<syn::WherePredicate>>,
+    pub skip: bool,
+    pub rename: Option<syn::LitStr>,
+    /// #[debug(alt = "...")], the format string used by `{:#?}`
+    pub alt: Option<syn::LitStr>,
+}
+
+/// Store how the field is printed. Only one formatting attribute can be given per field.
+fn set_format(
+    slot: &mut Option<FieldFormat>,
+    value: FieldFormat,
+    tokens: impl ToTokens,
+) -> syn::Result<()> {
+    if slot.is_some() {
+        return Err(syn::Error::new_spanned(
+            tokens,
+            "a field accepts only one formatting debug attribute",
+        ));
+    }
+    *slot = Some(value);
+    Ok(())
+}
+
+/// parse the optional hint of `redact(len)` or `redact(hash)`
+fn parse_redaction(meta: &syn::meta::ParseNestedMeta) -> syn::Result<Redaction> {
+    if !meta.input.peek(syn::token::Paren) {
+        return Ok(Redaction::Plain);
+    }
+
+    let mut redaction = None;
+    meta.parse_nested_meta(|hint| {
+        let value = if hint.path.is_ident("len") {
+            Redaction::Len
+        } else if hint.path.is_ident("hash") {
+            Redaction::Hash
+        } else {
+            return Err(hint.error("expected `redact(len)` or `redact(hash)`"));
+        };
+        set_once(
+            &mut redaction,
+            value,
+            &hint.path,
+            "`redact` debug attribute",
+        )
+    })?;
+    Ok(redaction.unwrap_or(Redaction::Plain))
+}
+
+/// parse the number of `max_items = N` or `max_len = N`
+pub fn parse_limit(meta: &syn::meta::ParseNestedMeta) -> syn::Result<syn::LitInt> {
+    let litint: syn::LitInt = meta.value()?.parse()?;
+    litint.base10_parse::<usize>()?;
+    Ok(litint)
+}
+
+/// Build the format string of `hex`, `bin` or `oct`, e.g. `0x{:08x}` for `hex(8)`.
+fn parse_radix(
+    meta: &syn::meta::ParseNestedMeta,
+    prefix: &str,
+    ty: &str,
+) -> syn::Result<syn::LitStr> {
+    let format = if meta.input.peek(syn::token::Paren) {
+        let content;
+        syn::parenthesized!(content in meta.input);
+        let width: syn::LitInt = content.parse()?;
+        format!("{}{{:0{}{}}}", prefix, width.base10_parse::<usize>()?, ty)
+    } else {
+        format!("{}{{:{}}}", prefix, ty)
+    };
+    Ok(syn::LitStr::new(&format, meta.path.span()))
+}
+
+/// parse the string of `bound = "..."` as comma separated where predicates
+pub fn parse_bound(meta: &syn::meta::ParseNestedMeta) -> syn::Result<Vec<syn::WherePredicate>> {
+    let litstr: syn::LitStr = meta.value()?.parse()?;
+    let predicates =
+        litstr.parse_with(Punctuated::<syn::WherePredicate, Token![,]>::parse_terminated)?;
+    Ok(predicates.into_iter().collect())
+}
+
+/// Whether `debug(bytes)` can print the type, i.e. an unsigned integer which fits in u64.
+fn is_byte_count(ty: &syn::Type) -> bool {
+    match ty {
+        syn::Type::Path(syn::TypePath { qself: None, path }) => {
+            path.get_ident().is_some_and(|ident| {
+                ["u8", "u16", "u32", "u64", "usize"]
+                    .iter()
+                    .any(|ty| ident == ty)
+            })
+        }
+        _ => false,
+    }
+}
+
+/// Whether `max_items` prints the type as a map, e.g. `HashMap<K, V>` or `&BTreeMap<K, V>`
+fn is_map(ty: &syn::Type) -> bool {
+    match ty {
+        syn::Type::Reference(reference) => is_map(&reference.elem),
+        syn::Type::Path(path) => path
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "HashMap" || segment.ident == "BTreeMap"),
+        _ => false,
+    }
+}
+
+/// Parse the debug attributes of a field. Attributes of other macros, doc
+/// comments and lint attributes are ignored.
+pub fn extract_debug_attributes(field: &syn::Field) -> syn::Result<FieldAttributes> {
+    let mut field_attrs = FieldAttributes::default();
+    let mut skip = None;
+
+    for attr in field
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("debug"))
+    {
+        match &attr.meta {
+            syn::Meta::NameValue(named) => match &named.value {
+                syn::Expr::Lit(syn::ExprLit {
+                    lit: syn::Lit::Str(litstr),
+                    ..
+                }) => {
+                    format::validate_field_format(litstr)?;
+                    set_format(
+                        &mut field_attrs.format,
+                        FieldFormat::Template(litstr.clone()),
+                        attr,
+                    )?
+                }
+                value => {
+                    return Err(syn::Error::new_spanned(
+                        value,
+                        "expected string literal like `#[debug = \"...\"]`",
+                    ))
+                }
+            },
+            syn::Meta::List(_) => {
+                attr.parse_nested_meta(|meta| {
+                    if meta.path.is_ident("bound") {
+                        set_once(
+                            &mut field_attrs.bound,
+                            parse_bound(&meta)?,
+                            &meta.path,
+                            "`bound` debug attribute",
+                        )
+                    } else if meta.path.is_ident("skip") {
+                        set_once(
+                            &mut skip,
+                            meta.path.clone(),
+                            &meta.path,
+                            "`skip` debug attribute",
+                        )
+                    } else if meta.path.is_ident("rename") {
+                        set_once(
+                            &mut field_attrs.rename,
+                            meta.value()?.parse()?,
+                            &meta.path,
+                            "`rename` debug attribute",
+                        )
+                    } else if meta.path.is_ident("alt") {
+                        let litstr: syn::LitStr = meta.value()?.parse()?;
+                        format::validate_field_format(&litstr)?;
+                        set_once(
+                            &mut field_attrs.alt,
+                            litstr,
+                            &meta.path,
+                            "`alt` debug attribute",
+                        )
+                    } else if meta.path.is_ident("with") {
+                        let litstr: syn::LitStr = meta.value()?.parse()?;
+                        set_format(
+                            &mut field_attrs.format,
+                            FieldFormat::With(litstr.parse()?),
+                            &meta.path,
+                        )
+                    } else if meta.path.is_ident("expr") {
+                        let litstr: syn::LitStr = meta.value()?.parse()?;
+                        // 括弧の対応が取れていない場合のエラーは呼び出し側を指すので、リテラルの位置に付け替える
+                        let expr = litstr
+                            .parse()
+                            .map_err(|err| syn::Error::new_spanned(&litstr, err))?;
+                        set_format(&mut field_attrs.format, FieldFormat::Expr(expr), &meta.path)
+                    } else if meta.path.is_ident("max_items") {
+                        // マップは std と同じく `{k: v}` の形で出力する
+                        let max_items = parse_limit(&meta)?;
+                        let format = if is_map(&field.ty) {
+                            FieldFormat::MaxEntries(max_items)
+                        } else {
+                            FieldFormat::MaxItems(max_items)
+                        };
+                        set_format(&mut field_attrs.format, format, &meta.path)
+                    } else if meta.path.is_ident("max_len") {
+                        set_format(
+                            &mut field_attrs.format,
+                            FieldFormat::MaxLen(parse_limit(&meta)?),
+                            &meta.path,
+                        )
+                    } else if meta.path.is_ident("hex")
+                        || meta.path.is_ident("bin")
+                        || meta.path.is_ident("oct")
+                    {
+                        let litstr = match meta.path.get_ident() {
+                            Some(ident) if ident == "hex" => parse_radix(&meta, "0x", "x")?,
+                            Some(ident) if ident == "bin" => parse_radix(&meta, "0b", "b")?,
+                            _ => parse_radix(&meta, "0o", "o")?,
+                        };
+                        set_format(
+                            &mut field_attrs.format,
+                            FieldFormat::Template(litstr),
+                            &meta.path,
+                        )
+                    } else if meta.path.is_ident("bytes") {
+                        set_format(&mut field_attrs.format, FieldFormat::Bytes, &meta.path)
+                    } else if meta.path.is_ident("redact") {
+                        let redaction = parse_redaction(&meta)?;
+                        set_format(
+                            &mut field_attrs.format,
+                            FieldFormat::Redact(redaction),
+                            &meta.path,
+                        )
+                    } else {
+                        Err(meta.error("unknown debug attribute"))
+                    }
+                })?;
+            }
+            syn::Meta::Path(path) => {
+                return Err(syn::Error::new_spanned(
+                    path,
+                    "expected `#[debug = \"...\"]` or `#[debug(...)]`",
+                ))
+            }
+        }
+    }
+
+    if let (Some(path), true) = (
+        &skip,
+        field_attrs.format.is_some() || field_attrs.alt.is_some(),
+    ) {
+        return Err(syn::Error::new_spanned(
+            path,
+            "a skipped field cannot have a formatting debug attribute",
+        ));
+    }
+    // alt は書式文字列か既定の Debug の出力を置き換えるものなので、他の書式とは組み合わせられない
+    if let (Some(alt), Some(format)) = (&field_attrs.alt, &field_attrs.format) {
+        if !matches!(format, FieldFormat::Template(_)) {
+            return Err(syn::Error::new_spanned(
+                alt,
+                "`alt` can only be combined with `#[debug = \"...\"]`",
+            ));
+        }
+    }
+    // 負の値や u64 に収まらない値は u64 に変換すると壊れるので、符号なし整数だけを受け付ける
+    if matches!(field_attrs.format, Some(FieldFormat::Bytes)) && !is_byte_count(&field.ty) {
+        return Err(syn::Error::new_spanned(
+            &field.ty,
+            "`debug(bytes)` accepts only fields of type u8, u16, u32, u64 or usize",
+        ));
+    }
+    field_attrs.skip = skip.is_some();
+
+    Ok(field_attrs)
+}
+
+/// Generate the `&dyn Debug` expression printed for a field bound by reference to `binding`.
+pub fn generate_value(attrs: &FieldAttributes, binding: &syn::Ident) -> proc_macro2::TokenStream {
+    let value = match &attrs.format {
+        Some(FieldFormat::Template(format)) => quote! { &format_args!(#format, #binding) },
+        Some(FieldFormat::Redact(Redaction::Plain)) => quote! { &format_args!("[REDACTED]") },
+        Some(FieldFormat::Redact(Redaction::Len)) => {
+            quote! { &format_args!("[REDACTED; len={}]", #binding.len()) }
+        }
+        Some(FieldFormat::Redact(Redaction::Hash)) => quote! {
+            &format_args!("[REDACTED; hash={:016x}]", {
+                let mut hasher = std::collections::hash_map::DefaultHasher::new();
+                std::hash::Hash::hash(#binding, &mut hasher);
+                std::hash::Hasher::finish(&hasher)
+            })
+        },
+        Some(FieldFormat::With(path)) => {
+            let debug_with = debug_with_ident();
+            quote! { &#debug_with(#binding, #path) }
+        }
+        // 式の中では self と他のフィールドの束縛を参照できる
+        Some(FieldFormat::Expr(expr)) => quote! { &(#expr) },
+        // iter() は自動参照外しで呼ばれるので、参照のフィールドでも要素を列挙できる
+        Some(FieldFormat::MaxItems(max_items)) => {
+            let debug_items = debug_items_ident();
+            quote! { &#debug_items((#binding).iter(), #max_items) }
+        }
+        Some(FieldFormat::MaxEntries(max_items)) => {
+            let debug_entries = debug_entries_ident();
+            quote! { &#debug_entries((#binding).iter(), #max_items) }
+        }
+        Some(FieldFormat::MaxLen(max_len)) => {
+            let debug_chars = debug_chars_ident();
+            quote! { &#debug_chars(#binding, #max_len) }
+        }
+        // 符号なし整数のフィールドだけを受け付けているので、u64 に変換してから単位を付ける
+        Some(FieldFormat::Bytes) => {
+            let debug_bytes = debug_bytes_ident();
+            quote! { &#debug_bytes(*#binding as u64) }
+        }
+        None => quote! { #binding },
+    };
+
+    // {:#?} のときだけ使う書式があれば、フォーマッタのフラグで切り替える
+    match alternate_format(attrs) {
+        Some(alt) => {
+            let debug_alt = debug_alt_ident();
+            quote! { &#debug_alt(#value, format_args!(#alt, #binding)) }
+        }
+        None => value,
+    }
+}
+
+/// The format string of the field used by `{:#?}` instead of the usual output
+pub fn alternate_format(attrs: &FieldAttributes) -> Option<syn::LitStr> {
+    match (&attrs.alt, &attrs.format) {
+        (Some(alt), _) => Some(alt.clone()),
+        (None, Some(FieldFormat::Template(format))) => format::alternate_field_format(format),
+        _ => None,
+    }
+}
+
+fn debug_alt_ident() -> syn::Ident {
+    syn::Ident::new("__DebugAlt", Span::mixed_site())
+}
+
+/// Generate the wrapper which switches to the format of `debug(alt = "...")` in pretty mode.
+fn generate_debug_alt() -> proc_macro2::TokenStream {
+    let debug_alt = debug_alt_ident();
+    quote! {
+        struct #debug_alt<'a>(&'a dyn std::fmt::Debug, std::fmt::Arguments<'a>);
+
+        impl std::fmt::Debug for #debug_alt<'_> {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                if f.alternate() {
+                    std::fmt::Debug::fmt(&self.1, f)
+                } else {
+                    std::fmt::Debug::fmt(self.0, f)
+                }
+            }
+        }
+    }
+}
+
+fn debug_with_ident() -> syn::Ident {
+    syn::Ident::new("__DebugWith", Span::mixed_site())
+}
+
+/// Generate the wrapper which implements Debug by calling the function given by `debug(with = "...")`.
+fn generate_debug_with() -> proc_macro2::TokenStream {
+    let debug_with = debug_with_ident();
+    quote! {
+        struct #debug_with<'a, T: ?Sized>(
+            &'a T,
+            fn(&T, &mut std::fmt::Formatter<'_>) -> std::fmt::Result,
+        );
+
+        impl<T: ?Sized> std::fmt::Debug for #debug_with<'_, T> {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                (self.1)(self.0, f)
+            }
+        }
+    }
+}
+
+fn debug_items_ident() -> syn::Ident {
+    syn::Ident::new("__DebugItems", Span::mixed_site())
+}
+
+/// Generate the wrapper which prints only the first items given by `debug(max_items = N)`.
+fn generate_debug_items() -> proc_macro2::TokenStream {
+    let debug_items = debug_items_ident();
+    quote! {
+        struct #debug_items<I>(I, usize);
+
+        impl<I> std::fmt::Debug for #debug_items<I>
+        where
+            I: Iterator + Clone,
+            I::Item: std::fmt::Debug,
+        {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                let mut list = f.debug_list();
+                let mut iter = self.0.clone();
+                list.entries(iter.by_ref().take(self.1));
+
+                let rest = iter.count();
+                if rest > 0 {
+                    list.entry(&format_args!("... ({} more)", rest));
+                }
+                list.finish()
+            }
+        }
+    }
+}
+
+fn debug_entries_ident() -> syn::Ident {
+    syn::Ident::new("__DebugEntries", Span::mixed_site())
+}
+
+/// Generate the wrapper which prints only the first entries of a map given by
+/// `debug(max_items = N)`. The omitted entries are shown as `..`, since a map has no place for
+/// their count.
+fn generate_debug_entries() -> proc_macro2::TokenStream {
+    let debug_entries = debug_entries_ident();
+    quote! {
+        struct #debug_entries<I>(I, usize);
+
+        impl<I, K, V> std::fmt::Debug for #debug_entries<I>
+        where
+            I: Iterator<Item = (K, V)> + Clone,
+            K: std::fmt::Debug,
+            V: std::fmt::Debug,
+        {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                let mut map = f.debug_map();
+                let mut iter = self.0.clone();
+                map.entries(iter.by_ref().take(self.1));
+
+                if iter.next().is_some() {
+                    map.finish_non_exhaustive()
+                } else {
+                    map.finish()
+                }
+            }
+        }
+    }
+}
+
+fn debug_chars_ident() -> syn::Ident {
+    syn::Ident::new("__DebugChars", Span::mixed_site())
+}
+
+/// Generate the wrapper which prints only the first characters given by `debug(max_len = N)`.
+fn generate_debug_chars() -> proc_macro2::TokenStream {
+    let debug_chars = debug_chars_ident();
+    quote! {
+        struct #debug_chars<'a>(&'a str, usize);
+
+        impl std::fmt::Debug for #debug_chars<'_> {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                match self.0.char_indices().nth(self.1) {
+                    Some((end, _)) => {
+                        let rest = self.0[end..].chars().count();
+                        write!(f, "{:?}... ({} more)", &self.0[..end], rest)
+                    }
+                    None => std::fmt::Debug::fmt(self.0, f),
+                }
+            }
+        }
+    }
+}
+
+fn debug_bytes_ident() -> syn::Ident {
+    syn::Ident::new("__DebugBytes", Span::mixed_site())
+}
+
+/// Generate the wrapper which prints a size given by `debug(bytes)` such as `1.5 KiB`.
+fn generate_debug_bytes() -> proc_macro2::TokenStream {
+    let debug_bytes = debug_bytes_ident();
+    quote! {
+        struct #debug_bytes(u64);
+
+        impl std::fmt::Debug for #debug_bytes {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                const UNITS: [&str; 6] = ["KiB", "MiB", "GiB", "TiB", "PiB", "EiB"];
+                if self.0 < 1024 {
+                    return write!(f, "{} B", self.0);
+                }
+
+                let mut size = self.0 as f64 / 1024.0;
+                let mut unit = 0;
+                while size >= 1024.0 && unit < UNITS.len() - 1 {
+                    size /= 1024.0;
+                    unit += 1;
+                }
+                write!(f, "{:.1} {}", size, UNITS[unit])
+            }
+        }
+    }
+}
+
+/// Generate the wrappers used by the fields, which are defined inside `fmt`.
+pub fn generate_wrappers<'a>(
+    fields: impl Iterator<Item = &'a FieldAttributes> + Clone,
+) -> proc_macro2::TokenStream {
+    let uses = |matches: fn(&FieldFormat) -> bool| {
+        fields
+            .clone()
+            .any(|attrs| attrs.format.as_ref().is_some_and(matches))
+    };
+
+    let mut wrappers = quote! {};
+    if uses(|format| matches!(format, FieldFormat::With(_))) {
+        wrappers.extend(generate_debug_with());
+    }
+    if uses(|format| matches!(format, FieldFormat::MaxItems(_))) {
+        wrappers.extend(generate_debug_items());
+    }
+    if uses(|format| matches!(format, FieldFormat::MaxEntries(_))) {
+        wrappers.extend(generate_debug_entries());
+    }
+    if uses(|format| matches!(format, FieldFormat::MaxLen(_))) {
+        wrappers.extend(generate_debug_chars());
+    }
+    if uses(|format| matches!(format, FieldFormat::Bytes)) {
+        wrappers.extend(generate_debug_bytes());
+    }
+    if fields
+        .clone()
+        .any(|attrs| alternate_format(attrs).is_some())
+    {
+        wrappers.extend(generate_debug_alt());
+    }
+    wrappers
+}
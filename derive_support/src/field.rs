@@ -0,0 +1,41 @@
+//! Helpers for the fields of a struct or an enum variant which are matched and
+//! printed by the generated impls.
+
+use proc_macro2::{Span, TokenTree};
+use quote::format_ident;
+use syn::ext::IdentExt;
+
+/// The binding of a field in the match pattern (the name of the field, or `__self_0` for tuple fields)
+pub fn binding(field: &syn::Field, index: usize) -> syn::Ident {
+    match &field.ident {
+        Some(ident) => ident.clone(),
+        None => format_ident!("__self_{}", index),
+    }
+}
+
+/// The index of the field which a placeholder of a template refers to
+pub fn find<'a>(fields: impl IntoIterator<Item = &'a syn::Field>, arg: &str) -> Option<usize> {
+    // 名前付きフィールドは名前で、タプルのフィールドは {0} のような番号で参照する
+    fields
+        .into_iter()
+        .enumerate()
+        .position(|(i, field)| match &field.ident {
+            Some(ident) => ident.unraw() == arg,
+            None => arg == i.to_string(),
+        })
+}
+
+/// Whether the type refers to one of the type parameters of the item
+pub fn mentions_type_param(tokens: proc_macro2::TokenStream, generics: &syn::Generics) -> bool {
+    tokens.into_iter().any(|token| match token {
+        TokenTree::Ident(ident) => generics.type_params().any(|param| param.ident == ident),
+        TokenTree::Group(group) => mentions_type_param(group.stream(), generics),
+        _ => false,
+    })
+}
+
+/// A local variable of the generated code, such as the formatter of `fmt`
+pub fn local_ident(name: &str) -> syn::Ident {
+    // 利用者のフィールド名と衝突しないように、生成するローカル変数は mixed_site で定義する
+    syn::Ident::new(name, Span::mixed_site())
+}
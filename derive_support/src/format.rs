@@ -0,0 +1,361 @@
+//! A parser of format strings, following the syntax of `std::fmt`.
+
+use syn::{ext::IdentExt, parse::Parser};
+
+/// A `{arg:spec}` placeholder
+pub struct Placeholder<'a> {
+    /// The argument as written, empty for the next positional argument
+    pub arg: &'a str,
+    /// The byte offset of `arg` in the format string
+    pub offset: usize,
+    pub spec: Spec<'a>,
+}
+
+/// The part after `:` of a placeholder
+#[derive(Default)]
+pub struct Spec<'a> {
+    pub width: Option<Count>,
+    pub precision: Option<Count>,
+    /// The formatting trait, e.g. `?`, `x` or `x?`. Empty for Display.
+    pub ty: &'a str,
+    pub alternate: bool,
+    /// The byte offset in the format string where the `#` flag is or would be written
+    pub alternate_offset: usize,
+}
+
+impl Spec<'_> {
+    pub fn is_debug(&self) -> bool {
+        self.ty.ends_with('?')
+    }
+
+    /// The trait of std::fmt which the argument must implement
+    pub fn trait_path(&self) -> syn::Path {
+        match self.ty {
+            "" => syn::parse_quote!(std::fmt::Display),
+            "x" => syn::parse_quote!(std::fmt::LowerHex),
+            "X" => syn::parse_quote!(std::fmt::UpperHex),
+            "o" => syn::parse_quote!(std::fmt::Octal),
+            "b" => syn::parse_quote!(std::fmt::Binary),
+            "e" => syn::parse_quote!(std::fmt::LowerExp),
+            "E" => syn::parse_quote!(std::fmt::UpperExp),
+            "p" => syn::parse_quote!(std::fmt::Pointer),
+            _ => syn::parse_quote!(std::fmt::Debug),
+        }
+    }
+}
+
+/// The width or precision of a placeholder
+pub enum Count {
+    /// `{:8}`
+    Literal,
+    /// `{:width$}` or `{:1$}`
+    Arg,
+    /// `{:.*}`
+    Star,
+}
+
+const FORMAT_TRAITS: &[&str] = &["", "?", "x?", "X?", "x", "X", "o", "b", "e", "E", "p"];
+
+/// Parse the placeholders of a format string.
+pub fn parse(format: &str) -> Result<Vec<Placeholder<'_>>, String> {
+    let mut placeholders = Vec::new();
+    let mut rest = format;
+
+    while let Some(pos) = rest.find(['{', '}']) {
+        let tail = &rest[pos..];
+        if let Some(after) = tail.strip_prefix("{{").or_else(|| tail.strip_prefix("}}")) {
+            rest = after;
+            continue;
+        }
+        if tail.starts_with('}') {
+            return Err("invalid format string: unmatched `}` found".to_owned());
+        }
+
+        let end = tail
+            .find('}')
+            .ok_or("invalid format string: expected `}` but string was terminated")?;
+        let offset = format.len() - tail.len() + 1;
+        placeholders.push(parse_placeholder(&tail[1..end], offset)?);
+        rest = &tail[end + 1..];
+    }
+    Ok(placeholders)
+}
+
+fn parse_placeholder(inner: &str, offset: usize) -> Result<Placeholder<'_>, String> {
+    let (arg, spec) = match inner.split_once(':') {
+        Some((arg, spec)) => {
+            let mut spec = parse_spec(spec)?;
+            spec.alternate_offset += offset + arg.len() + 1;
+            (arg, spec)
+        }
+        None => (inner, Spec::default()),
+    };
+    if !arg.is_empty() && !is_argument(arg) {
+        return Err(format!(
+            "invalid format string: invalid argument name `{}`",
+            arg
+        ));
+    }
+    Ok(Placeholder { arg, offset, spec })
+}
+
+/// `[[fill]align][sign]['#']['0'][width]['.' precision][type]`
+fn parse_spec(spec: &str) -> Result<Spec<'_>, String> {
+    let mut rest = spec;
+    let mut parsed = Spec::default();
+
+    // fill は任意の 1 文字なので、2 文字目が align かどうかを先に確認する
+    let mut chars = rest.chars();
+    match (chars.next(), chars.next()) {
+        (Some(fill), Some('<' | '^' | '>')) => rest = &rest[fill.len_utf8() + 1..],
+        (Some('<' | '^' | '>'), _) => rest = &rest[1..],
+        _ => {}
+    }
+    rest = rest.strip_prefix(['+', '-']).unwrap_or(rest);
+    parsed.alternate_offset = spec.len() - rest.len();
+    if let Some(after) = rest.strip_prefix('#') {
+        parsed.alternate = true;
+        rest = after;
+    }
+    // `0` の直後に `$` が続く場合は幅ではなく引数の指定
+    if rest.starts_with('0') && !rest[1..].starts_with('$') {
+        rest = &rest[1..];
+    }
+
+    let (width, after) = parse_count(rest);
+    parsed.width = width;
+    rest = after;
+
+    if let Some(after) = rest.strip_prefix('.') {
+        let (precision, after) = match after.strip_prefix('*') {
+            Some(after) => (Some(Count::Star), after),
+            None => parse_count(after),
+        };
+        if precision.is_none() {
+            return Err("invalid format string: expected precision after `.`".to_owned());
+        }
+        parsed.precision = precision;
+        rest = after;
+    }
+
+    if !FORMAT_TRAITS.contains(&rest) {
+        return Err(format!("unknown format trait `{}`", rest));
+    }
+    parsed.ty = rest;
+    Ok(parsed)
+}
+
+/// Parse `8`, `1$` or `name$` at the start of the input.
+fn parse_count(input: &str) -> (Option<Count>, &str) {
+    let end = input
+        .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .unwrap_or(input.len());
+    let (word, rest) = input.split_at(end);
+
+    if let Some(after) = rest.strip_prefix('$') {
+        if is_argument(word) {
+            return (Some(Count::Arg), after);
+        }
+    }
+    let digits = input
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(input.len());
+    if digits > 0 {
+        (Some(Count::Literal), &input[digits..])
+    } else {
+        (None, input)
+    }
+}
+
+/// A positional index or an identifier. Keywords are allowed so that a field
+/// like `r#type` can be referred to as `{type}`.
+fn is_argument(arg: &str) -> bool {
+    arg.chars().all(|c| c.is_ascii_digit()) || syn::Ident::parse_any.parse_str(arg).is_ok()
+}
+
+/// Check that the format string of `#[debug = "..."]` prints the field with
+/// exactly one placeholder.
+pub fn validate_field_format(litstr: &syn::LitStr) -> syn::Result<()> {
+    let value = litstr.value();
+    let placeholders = parse(&value).map_err(|msg| syn::Error::new_spanned(litstr, msg))?;
+
+    let [placeholder] = &placeholders[..] else {
+        return Err(syn::Error::new_spanned(
+            litstr,
+            format!(
+                "format string must contain exactly one placeholder for the field, found {}",
+                placeholders.len()
+            ),
+        ));
+    };
+
+    if !matches!(placeholder.arg, "" | "0") {
+        return Err(syn::Error::new_spanned(
+            litstr,
+            "the placeholder of the field must be `{}` or `{0}`",
+        ));
+    }
+    check_counts(litstr, placeholder)
+}
+
+/// The trait which the field must implement to be printed with the format
+/// string of `#[debug = "..."]`, e.g. LowerHex for `0x{:x}`.
+pub fn field_format_trait(litstr: &syn::LitStr) -> syn::Path {
+    let value = litstr.value();
+    match parse(&value).as_deref() {
+        Ok([placeholder]) => placeholder.spec.trait_path(),
+        _ => syn::parse_quote!(std::fmt::Debug),
+    }
+}
+
+/// The format string of `#[debug = "..."]` used by `{:#?}`, where the `#` flag
+/// is added to the Debug placeholder so that nested values are pretty-printed
+/// as well. None if the format string already does so, or formats the field
+/// with another trait.
+pub fn alternate_field_format(litstr: &syn::LitStr) -> Option<syn::LitStr> {
+    let value = litstr.value();
+    let placeholders = parse(&value).ok()?;
+    let [placeholder] = &placeholders[..] else {
+        return None;
+    };
+    if !placeholder.spec.is_debug() || placeholder.spec.alternate {
+        return None;
+    }
+
+    let mut alternate = value.clone();
+    alternate.insert(placeholder.spec.alternate_offset, '#');
+    Some(syn::LitStr::new(&alternate, litstr.span()))
+}
+
+/// Width and precision taken from other arguments are not supported, since
+/// the arguments passed to `format_args!` are generated.
+pub fn check_counts(litstr: &syn::LitStr, placeholder: &Placeholder) -> syn::Result<()> {
+    let counts = [&placeholder.spec.width, &placeholder.spec.precision];
+    if counts
+        .iter()
+        .any(|count| matches!(count, Some(Count::Arg | Count::Star)))
+    {
+        return Err(syn::Error::new_spanned(
+            litstr,
+            "width and precision must be given as numbers",
+        ));
+    }
+    Ok(())
+}
+
+/// A template such as `debug(fmt = "...")` whose placeholders are rewritten to
+/// positional arguments, e.g. `Point({x}, {y:?})` to `Point({0}, {1:?})`.
+pub struct Template {
+    pub format: String,
+    /// The index of the field passed as each positional argument, together
+    /// with the traits the field is formatted with
+    pub args: Vec<(usize, Vec<syn::Path>)>,
+}
+
+/// Parse the template given to `attribute`, e.g. `#[display("...")]`.
+/// `field_index` looks up the field referenced by a placeholder.
+pub fn parse_template(
+    litstr: &syn::LitStr,
+    attribute: &str,
+    field_index: impl Fn(&str) -> Option<usize>,
+) -> syn::Result<Template> {
+    let value = litstr.value();
+    let placeholders = parse(&value).map_err(|msg| syn::Error::new_spanned(litstr, msg))?;
+
+    let mut format = String::new();
+    let mut args: Vec<(usize, Vec<syn::Path>)> = Vec::new();
+    let mut copied = 0;
+    for placeholder in &placeholders {
+        check_counts(litstr, placeholder)?;
+        if placeholder.arg.is_empty() {
+            return Err(syn::Error::new_spanned(
+                litstr,
+                format!(
+                    "placeholders of `{}` must name a field, like `{{x}}` or `{{0}}`",
+                    attribute
+                ),
+            ));
+        }
+        let Some(index) = field_index(placeholder.arg) else {
+            return Err(syn::Error::new_spanned(
+                litstr,
+                format!("no field `{}` to format", placeholder.arg),
+            ));
+        };
+
+        // 同じフィールドは一つの位置引数にまとめる
+        let position = match args.iter().position(|(i, _)| *i == index) {
+            Some(position) => position,
+            None => {
+                args.push((index, vec![]));
+                args.len() - 1
+            }
+        };
+        let trait_path = placeholder.spec.trait_path();
+        if !args[position].1.contains(&trait_path) {
+            args[position].1.push(trait_path);
+        }
+
+        format.push_str(&value[copied..placeholder.offset]);
+        format.push_str(&position.to_string());
+        copied = placeholder.offset + placeholder.arg.len();
+    }
+    format.push_str(&value[copied..]);
+
+    Ok(Template { format, args })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn placeholders_and_escapes() {
+        let placeholders = parse("{{x}} {x:>8.2} {0:#x?} {}").unwrap();
+        let args = placeholders.iter().map(|p| p.arg).collect::<Vec<_>>();
+        assert_eq!(args, ["x", "0", ""]);
+
+        let spec = &placeholders[0].spec;
+        assert!(matches!(spec.width, Some(Count::Literal)));
+        assert!(matches!(spec.precision, Some(Count::Literal)));
+        assert_eq!(spec.ty, "");
+
+        let spec = &placeholders[1].spec;
+        assert!(spec.alternate && spec.is_debug());
+        assert_eq!(spec.ty, "x?");
+    }
+
+    #[test]
+    fn counts_from_arguments() {
+        let placeholders = parse("{:width$.*} {:0$}").unwrap();
+        assert!(matches!(placeholders[0].spec.width, Some(Count::Arg)));
+        assert!(matches!(placeholders[0].spec.precision, Some(Count::Star)));
+        assert!(matches!(placeholders[1].spec.width, Some(Count::Arg)));
+    }
+
+    #[test]
+    fn invalid_format_strings() {
+        assert!(parse("{").is_err());
+        assert!(parse("}").is_err());
+        assert!(parse("{:.}").is_err());
+        assert!(parse("{:z}").is_err());
+        assert!(parse("{1x}").is_err());
+    }
+
+    #[test]
+    fn template_arguments() {
+        let litstr = syn::LitStr::new("{y}/{x:?}/{y:x}", proc_macro2::Span::call_site());
+        let template = parse_template(&litstr, "fmt", |arg| match arg {
+            "x" => Some(0),
+            "y" => Some(1),
+            _ => None,
+        })
+        .unwrap();
+
+        // 同じフィールドは一つの位置引数にまとめ、使われるトレイトを集める
+        assert_eq!(template.format, "{0}/{1:?}/{0:x}");
+        let indices = template.args.iter().map(|(i, _)| *i).collect::<Vec<_>>();
+        assert_eq!(indices, [1, 0]);
+        assert_eq!(template.args[0].1.len(), 2);
+    }
+}
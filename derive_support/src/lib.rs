@@ -0,0 +1,32 @@
+//! Attribute parsing shared by `derive_builder`, `derive_debug`,
+//! `derive_display` and `derive_error`.
+//!
+//! `format` parses the format strings given to the attributes, so that
+//! mistakes are reported on the attribute instead of deep inside the generated
+//! `format_args!`. `debug` parses the field level `#[debug(...)]` attributes
+//! and generates the values printed for them. `field` binds and looks up the
+//! fields printed by the generated impls.
+
+use quote::ToTokens;
+
+pub mod debug;
+pub mod field;
+pub mod format;
+
+/// Store the value unless the same attribute has already been given.
+/// `attribute` describes the attribute in the error, e.g. ``"`skip` debug attribute"``.
+pub fn set_once<T>(
+    slot: &mut Option<T>,
+    value: T,
+    tokens: impl ToTokens,
+    attribute: &str,
+) -> syn::Result<()> {
+    if slot.is_some() {
+        return Err(syn::Error::new_spanned(
+            tokens,
+            format!("duplicate {}", attribute),
+        ));
+    }
+    *slot = Some(value);
+    Ok(())
+}
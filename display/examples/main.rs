@@ -0,0 +1,23 @@
+use derive_display::CustomDisplay;
+
+#[derive(CustomDisplay)]
+#[display("{name} (0b{bitmask:08b})")]
+pub struct Field<T> {
+    name: T,
+    bitmask: u8,
+}
+
+// impl<T: std::fmt::Display> std::fmt::Display for Field<T> {
+//     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+//         write!(f, "{} (0b{:08b})", self.name, self.bitmask)
+//     }
+// }
+
+fn main() {
+    let f = Field {
+        name: "F",
+        bitmask: 0b00011100,
+    };
+
+    assert_eq!(f.to_string(), "F (0b00011100)");
+}
@@ -0,0 +1,171 @@
+use proc_macro::TokenStream;
+use quote::{quote, ToTokens};
+use syn::{ext::IdentExt, parse_macro_input, DeriveInput};
+
+use derive_support::{field, format};
+
+#[proc_macro_derive(CustomDisplay, attributes(display))]
+pub fn derive(input: TokenStream) -> TokenStream {
+    let parsed = parse_macro_input!(input as DeriveInput);
+
+    match expand(parsed) {
+        Ok(expanded) => expanded.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+/// Parse the template of `#[display("...")]`. Attributes of other macros are ignored.
+fn extract_template(attrs: &[syn::Attribute]) -> syn::Result<Option<syn::LitStr>> {
+    let mut template = None;
+
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("display")) {
+        if template.is_some() {
+            return Err(syn::Error::new_spanned(
+                attr,
+                "duplicate `#[display(\"...\")]` attribute",
+            ));
+        }
+        template = Some(attr.parse_args::<syn::LitStr>()?);
+    }
+    Ok(template)
+}
+
+/// Generate the match arm which writes the template of a struct or a variant, and collect the
+/// bounds required by the fields it prints.
+fn generate_arm(
+    path: proc_macro2::TokenStream,
+    fields: &syn::Fields,
+    template: &syn::LitStr,
+    formatter: &syn::Ident,
+    generics: &syn::Generics,
+    bounds: &mut Vec<syn::WherePredicate>,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let parsed = format::parse_template(template, "#[display(\"...\")]", |arg| {
+        field::find(fields, arg)
+    })?;
+
+    let fields: Vec<_> = fields.iter().collect();
+    for (i, traits) in &parsed.args {
+        let ty = &fields[*i].ty;
+        if field::mentions_type_param(ty.to_token_stream(), generics) {
+            bounds.extend(
+                traits.iter().map(|trait_path| -> syn::WherePredicate {
+                    syn::parse_quote!(#ty: #trait_path)
+                }),
+            );
+        }
+    }
+
+    // テンプレートで参照するフィールドだけを束縛する
+    let used = |i: usize| parsed.args.iter().any(|(index, _)| *index == i);
+    let pattern = match fields.first().and_then(|field| field.ident.as_ref()) {
+        Some(_) => {
+            let bindings = fields
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| used(*i))
+                .map(|(i, field)| field::binding(field, i));
+            quote! { #path { #(ref #bindings,)* .. } }
+        }
+        None if fields.is_empty() => quote! { #path { .. } },
+        None => {
+            let bindings = fields.iter().enumerate().map(|(i, field)| {
+                if used(i) {
+                    let binding = field::binding(field, i);
+                    quote! { ref #binding }
+                } else {
+                    quote! { _ }
+                }
+            });
+            quote! { #path ( #(#bindings),* ) }
+        }
+    };
+
+    let format = syn::LitStr::new(&parsed.format, template.span());
+    let values = parsed
+        .args
+        .iter()
+        .map(|(i, _)| field::binding(fields[*i], *i));
+    Ok(quote! {
+        #pattern => #formatter.write_fmt(format_args!(#format, #(#values),*)),
+    })
+}
+
+fn expand(parsed: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let ident = &parsed.ident;
+    let template = extract_template(&parsed.attrs)?;
+    let formatter = field::local_ident("f");
+    let mut bounds = vec![];
+
+    let arms = match &parsed.data {
+        syn::Data::Struct(syn::DataStruct { fields, .. }) => {
+            let Some(template) = template else {
+                return Err(syn::Error::new(
+                    ident.span(),
+                    "missing `#[display(\"...\")]` attribute",
+                ));
+            };
+            vec![generate_arm(
+                quote! { Self },
+                fields,
+                &template,
+                &formatter,
+                &parsed.generics,
+                &mut bounds,
+            )?]
+        }
+        syn::Data::Enum(syn::DataEnum { variants, .. }) => {
+            if let Some(template) = template {
+                return Err(syn::Error::new_spanned(
+                    template,
+                    "`#[display(\"...\")]` of an enum must be given on each variant",
+                ));
+            }
+            variants
+                .iter()
+                .map(|variant| {
+                    let variant_ident = &variant.ident;
+                    match (extract_template(&variant.attrs)?, &variant.fields) {
+                        (Some(template), fields) => generate_arm(
+                            quote! { Self::#variant_ident },
+                            fields,
+                            &template,
+                            &formatter,
+                            &parsed.generics,
+                            &mut bounds,
+                        ),
+                        // ユニットバリアントはテンプレートがなければバリアント名を出力する
+                        (None, syn::Fields::Unit) => {
+                            let name = variant_ident.unraw().to_string();
+                            Ok(quote! { Self::#variant_ident => #formatter.write_str(#name), })
+                        }
+                        (None, _) => Err(syn::Error::new(
+                            variant_ident.span(),
+                            "missing `#[display(\"...\")]` attribute on a variant with fields",
+                        )),
+                    }
+                })
+                .collect::<syn::Result<Vec<_>>>()?
+        }
+        _ => {
+            return Err(syn::Error::new(
+                ident.span(),
+                "CustomDisplay can only be applied to struct or enum",
+            ))
+        }
+    };
+
+    let mut generics = parsed.generics.clone();
+    generics.make_where_clause().predicates.extend(bounds);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics std::fmt::Display for #ident #ty_generics #where_clause {
+            fn fmt(&self, #formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                match *self {
+                    #(#arms)*
+                }
+            }
+        }
+    })
+}
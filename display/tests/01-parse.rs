@@ -0,0 +1,16 @@
+// This test looks for a derive macro named CustomDisplay which accepts a
+// #[display("...")] template on the struct.
+//
+// Before moving on, have your derive macro parse the macro input as a
+// syn::DeriveInput syntax tree and the template as a syn::LitStr.
+
+use derive_display::CustomDisplay;
+
+#[derive(CustomDisplay)]
+#[display("{name}")]
+pub struct Field {
+    name: &'static str,
+    bitmask: u16,
+}
+
+fn main() {}
@@ -0,0 +1,57 @@
+// The template of #[display("...")] is written the same way as a format
+// string, where the placeholders name the fields of the struct. Any format
+// spec can be used, and a field can be printed more than once:
+//
+//     impl Display for Point {
+//         fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+//             match *self {
+//                 Self { ref x, ref y, .. } => f.write_fmt(format_args!("({0}, {1})", x, y)),
+//             }
+//         }
+//     }
+//
+// Fields which are not referenced by the template do not need to implement
+// anything.
+
+use derive_display::CustomDisplay;
+
+#[derive(CustomDisplay)]
+#[display("({x}, {y})")]
+pub struct Point {
+    x: i32,
+    y: i32,
+}
+
+pub struct Opaque;
+
+#[derive(CustomDisplay)]
+#[display("{name:>8}|{bitmask:#06x}|{bitmask:08b}|{{{name:?}}}")]
+pub struct Field {
+    name: &'static str,
+    bitmask: u16,
+    opaque: Opaque,
+}
+
+#[derive(CustomDisplay)]
+#[display("{type}")]
+pub struct Keyword {
+    r#type: &'static str,
+}
+
+#[derive(CustomDisplay)]
+#[display("unit")]
+pub struct Unit;
+
+fn main() {
+    assert_eq!(Point { x: 1, y: -2 }.to_string(), "(1, -2)");
+
+    let field = Field {
+        name: "F",
+        bitmask: 0b00011100,
+        opaque: Opaque,
+    };
+    assert_eq!(field.to_string(), r#"       F|0x001c|00011100|{"F"}"#);
+
+    assert_eq!(Keyword { r#type: "u8" }.to_string(), "u8");
+    assert_eq!(Unit.to_string(), "unit");
+}
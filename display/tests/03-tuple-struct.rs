@@ -0,0 +1,16 @@
+// Fields of a tuple struct are referenced by their position.
+
+use derive_display::CustomDisplay;
+
+#[derive(CustomDisplay)]
+#[display("#{0:02x}{1:02x}{2:02x}")]
+pub struct Rgb(u8, u8, u8);
+
+#[derive(CustomDisplay)]
+#[display("{1}")]
+pub struct Second((), &'static str);
+
+fn main() {
+    assert_eq!(Rgb(255, 128, 0).to_string(), "#ff8000");
+    assert_eq!(Second((), "second").to_string(), "second");
+}
@@ -0,0 +1,25 @@
+// For an enum the template is given on each variant. Unit variants without a
+// template print the name of the variant.
+
+use derive_display::CustomDisplay;
+
+#[derive(CustomDisplay)]
+pub enum Shape {
+    #[display("circle of radius {radius}")]
+    Circle { radius: f64 },
+    #[display("{0}x{1} rectangle")]
+    Rect(u32, u32),
+    Empty,
+    #[display("nothing")]
+    Nothing,
+}
+
+#[derive(CustomDisplay)]
+pub enum Never {}
+
+fn main() {
+    assert_eq!(Shape::Circle { radius: 1.5 }.to_string(), "circle of radius 1.5");
+    assert_eq!(Shape::Rect(3, 4).to_string(), "3x4 rectangle");
+    assert_eq!(Shape::Empty.to_string(), "Empty");
+    assert_eq!(Shape::Nothing.to_string(), "nothing");
+}
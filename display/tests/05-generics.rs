@@ -0,0 +1,45 @@
+// Fields whose type refers to a type parameter are required to implement the
+// trait of each placeholder they are printed with, so the generated impl gets
+// bounds like:
+//
+//     impl<T> Display for Labeled<T>
+//     where
+//         T: Display,
+//         Vec<T>: Debug,
+//
+// Type parameters which are never printed are left unbounded.
+
+use derive_display::CustomDisplay;
+use std::marker::PhantomData;
+
+#[derive(CustomDisplay)]
+#[display("{label}: {value} {history:?}")]
+pub struct Labeled<'a, T, U> {
+    label: &'a str,
+    value: T,
+    history: Vec<T>,
+    marker: PhantomData<U>,
+}
+
+#[derive(CustomDisplay)]
+pub enum Either<L, R> {
+    #[display("left {0:x}")]
+    Left(L),
+    #[display("right {0}")]
+    Right(R),
+}
+
+struct NotDisplay;
+
+fn main() {
+    let labeled = Labeled::<_, NotDisplay> {
+        label: "value",
+        value: 3,
+        history: vec![1, 2],
+        marker: PhantomData,
+    };
+    assert_eq!(labeled.to_string(), "value: 3 [1, 2]");
+
+    assert_eq!(Either::<u8, &str>::Left(255).to_string(), "left ff");
+    assert_eq!(Either::<u8, &str>::Right("r").to_string(), "right r");
+}
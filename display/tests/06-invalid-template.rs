@@ -0,0 +1,47 @@
+// Placeholders are checked against the fields when the derive is expanded,
+// and a template is required wherever the output cannot be guessed.
+
+use derive_display::CustomDisplay;
+
+#[derive(CustomDisplay)]
+#[display("({x}, {z})")]
+pub struct UnknownField {
+    x: i32,
+    y: i32,
+}
+
+#[derive(CustomDisplay)]
+#[display("({0}, {2})")]
+pub struct UnknownIndex(i32, i32);
+
+#[derive(CustomDisplay)]
+#[display("({}, {})")]
+pub struct Positional {
+    x: i32,
+    y: i32,
+}
+
+#[derive(CustomDisplay)]
+#[display("{x:q}")]
+pub struct UnknownTrait {
+    x: i32,
+}
+
+#[derive(CustomDisplay)]
+pub struct MissingTemplate {
+    x: i32,
+}
+
+#[derive(CustomDisplay)]
+pub enum MissingVariantTemplate {
+    Unit,
+    Tuple(i32),
+}
+
+#[derive(CustomDisplay)]
+#[display("shape")]
+pub enum OnEnum {
+    Circle,
+}
+
+fn main() {}
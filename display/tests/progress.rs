@@ -0,0 +1,10 @@
+#[test]
+fn tests() {
+    let t = trybuild::TestCases::new();
+    t.pass("tests/01-parse.rs");
+    t.pass("tests/02-struct-template.rs");
+    t.pass("tests/03-tuple-struct.rs");
+    t.pass("tests/04-enum.rs");
+    t.pass("tests/05-generics.rs");
+    t.compile_fail("tests/06-invalid-template.rs");
+}
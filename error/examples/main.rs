@@ -0,0 +1,49 @@
+use derive_error::Error;
+use std::error::Error as _;
+use std::io;
+
+#[derive(Error, Debug)]
+pub enum ConfigError {
+    #[error("failed to read {path}")]
+    Read {
+        path: String,
+        #[source]
+        cause: io::Error,
+    },
+    #[error("invalid number")]
+    Parse(#[from] std::num::ParseIntError),
+}
+
+// impl std::fmt::Display for ConfigError {
+//     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+//         match self {
+//             Self::Read { path, .. } => write!(f, "failed to read {}", path),
+//             Self::Parse(_) => write!(f, "invalid number"),
+//         }
+//     }
+// }
+//
+// impl std::error::Error for ConfigError {
+//     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+//         match self {
+//             Self::Read { cause, .. } => Some(cause),
+//             Self::Parse(source) => Some(source),
+//         }
+//     }
+// }
+//
+// impl From<std::num::ParseIntError> for ConfigError { ... }
+
+fn parse(input: &str) -> Result<u16, ConfigError> {
+    Ok(input.parse()?)
+}
+
+fn main() {
+    let error = parse("port").unwrap_err();
+
+    assert_eq!(error.to_string(), "invalid number");
+    assert_eq!(
+        error.source().unwrap().to_string(),
+        "invalid digit found in string"
+    );
+}
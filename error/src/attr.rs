@@ -0,0 +1,61 @@
+use derive_support::set_once;
+use proc_macro2::Span;
+use syn::spanned::Spanned;
+
+/// Values given by the #[source] and #[from] attributes of a field
+#[derive(Default)]
+pub(crate) struct FieldAttributes {
+    // エラーの位置を示すために属性のスパンを保持する
+    pub(crate) source: Option<Span>,
+    pub(crate) from: Option<Span>,
+}
+
+impl FieldAttributes {
+    /// `#[from]` makes the field the source as well
+    pub(crate) fn is_source(&self) -> bool {
+        self.source.is_some() || self.from.is_some()
+    }
+}
+
+/// Parse the #[source] and #[from] attributes of a field. Attributes of other
+/// macros, doc comments and lint attributes are ignored.
+pub(crate) fn extract_error_attributes(attrs: &[syn::Attribute]) -> syn::Result<FieldAttributes> {
+    let mut field_attrs = FieldAttributes::default();
+
+    for attr in attrs {
+        if attr.path().is_ident("source") {
+            attr.meta.require_path_only()?;
+            set_once(
+                &mut field_attrs.source,
+                attr.path().span(),
+                attr,
+                "`#[source]` attribute",
+            )?;
+        } else if attr.path().is_ident("from") {
+            attr.meta.require_path_only()?;
+            set_once(
+                &mut field_attrs.from,
+                attr.path().span(),
+                attr,
+                "`#[from]` attribute",
+            )?;
+        } else if attr.path().is_ident("error") {
+            return Err(syn::Error::new_spanned(
+                attr,
+                "`#[error(\"...\")]` is given on the struct or the variant, not on a field",
+            ));
+        }
+    }
+    Ok(field_attrs)
+}
+
+/// Parse the message of `#[error("...")]` given on the struct or a variant.
+pub(crate) fn extract_message(attrs: &[syn::Attribute]) -> syn::Result<Option<syn::LitStr>> {
+    let mut message = None;
+
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("error")) {
+        let litstr = attr.parse_args::<syn::LitStr>()?;
+        set_once(&mut message, litstr, attr, "`#[error]` attribute")?;
+    }
+    Ok(message)
+}
@@ -0,0 +1,360 @@
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use quote::{quote, ToTokens};
+use syn::{parse_macro_input, DeriveInput};
+
+use derive_support::{field, format};
+
+use attr::{extract_error_attributes, extract_message, FieldAttributes};
+
+mod attr;
+
+#[proc_macro_derive(Error, attributes(error, source, from))]
+pub fn derive(input: TokenStream) -> TokenStream {
+    let parsed = parse_macro_input!(input as DeriveInput);
+
+    match expand(parsed) {
+        Ok(expanded) => expanded.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+/// A field of the struct or of an enum variant together with its attributes
+struct ErrorField<'a> {
+    field: &'a syn::Field,
+    attrs: FieldAttributes,
+    binding: syn::Ident,
+}
+
+/// The struct itself or one of the variants of the enum
+struct Variant<'a> {
+    // Self または Self::Variant
+    path: proc_macro2::TokenStream,
+    fields: &'a syn::Fields,
+    error_fields: Vec<ErrorField<'a>>,
+    message: syn::LitStr,
+}
+
+impl Variant<'_> {
+    fn source(&self) -> Option<&ErrorField<'_>> {
+        self.error_fields.iter().find(|f| f.attrs.is_source())
+    }
+
+    fn from(&self) -> Option<&ErrorField<'_>> {
+        self.error_fields.iter().find(|f| f.attrs.from.is_some())
+    }
+}
+
+fn extract_variant<'a>(
+    path: proc_macro2::TokenStream,
+    ident: &syn::Ident,
+    attrs: &[syn::Attribute],
+    fields: &'a syn::Fields,
+) -> syn::Result<Variant<'a>> {
+    let Some(message) = extract_message(attrs)? else {
+        return Err(syn::Error::new(
+            ident.span(),
+            "missing `#[error(\"...\")]` message",
+        ));
+    };
+
+    let error_fields = fields
+        .iter()
+        .enumerate()
+        .map(|(i, field)| {
+            let binding = field::binding(field, i);
+            let attrs = extract_error_attributes(&field.attrs)?;
+            Ok(ErrorField {
+                field,
+                attrs,
+                binding,
+            })
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    let mut sources = error_fields.iter().filter(|f| f.attrs.is_source());
+    if let (Some(_), Some(second)) = (sources.next(), sources.next()) {
+        let span = second.attrs.source.or(second.attrs.from).unwrap();
+        return Err(syn::Error::new(
+            span,
+            "only one field can be the source of an error",
+        ));
+    }
+    // From で生成できるのはフィールドが一つだけの場合に限る
+    if let Some(span) = error_fields.iter().find_map(|f| f.attrs.from) {
+        if error_fields.len() != 1 {
+            return Err(syn::Error::new(
+                span,
+                "`#[from]` requires exactly one field",
+            ));
+        }
+    }
+
+    Ok(Variant {
+        path,
+        fields,
+        error_fields,
+        message,
+    })
+}
+
+/// Generate the pattern which binds the given fields by reference, e.g. `Self::Variant { ref a, .. }`.
+fn generate_pattern(variant: &Variant, used: impl Fn(usize) -> bool) -> proc_macro2::TokenStream {
+    let path = &variant.path;
+    let bindings = variant.error_fields.iter().enumerate().map(|(i, f)| {
+        if used(i) {
+            let binding = &f.binding;
+            quote! { ref #binding }
+        } else {
+            quote! { _ }
+        }
+    });
+
+    match variant.fields {
+        syn::Fields::Named(_) => {
+            let bindings = variant
+                .error_fields
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| used(*i))
+                .map(|(_, f)| &f.binding);
+            quote! { #path { #(ref #bindings,)* .. } }
+        }
+        syn::Fields::Unnamed(_) => quote! { #path ( #(#bindings),* ) },
+        syn::Fields::Unit => quote! { #path },
+    }
+}
+
+/// Generate the match arm which writes the message, and collect the bounds required by the
+/// fields it prints.
+fn generate_display_arm(
+    variant: &Variant,
+    formatter: &syn::Ident,
+    generics: &syn::Generics,
+    bounds: &mut Vec<syn::WherePredicate>,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let parsed = format::parse_template(&variant.message, "#[error(\"...\")]", |arg| {
+        field::find(variant.error_fields.iter().map(|f| f.field), arg)
+    })?;
+
+    for (i, traits) in &parsed.args {
+        let ty = &variant.error_fields[*i].field.ty;
+        if field::mentions_type_param(ty.to_token_stream(), generics) {
+            bounds.extend(
+                traits.iter().map(|trait_path| -> syn::WherePredicate {
+                    syn::parse_quote!(#ty: #trait_path)
+                }),
+            );
+        }
+    }
+
+    let pattern = generate_pattern(variant, |i| {
+        parsed.args.iter().any(|(index, _)| *index == i)
+    });
+    let format = syn::LitStr::new(&parsed.format, variant.message.span());
+    let values = parsed
+        .args
+        .iter()
+        .map(|(i, _)| &variant.error_fields[*i].binding);
+    Ok(quote! {
+        #pattern => #formatter.write_fmt(format_args!(#format, #(#values),*)),
+    })
+}
+
+fn as_dyn_error_ident() -> syn::Ident {
+    syn::Ident::new("__AsDynError", Span::mixed_site())
+}
+
+/// Generate the helper trait which converts both concrete errors and boxed `dyn Error`s into
+/// `&dyn Error`, since `Box<dyn Error>` itself does not implement Error.
+fn generate_as_dyn_error() -> proc_macro2::TokenStream {
+    let as_dyn_error = as_dyn_error_ident();
+    quote! {
+        trait #as_dyn_error {
+            fn as_dyn_error(&self) -> &(dyn std::error::Error + 'static);
+        }
+
+        impl<T: std::error::Error + 'static> #as_dyn_error for T {
+            fn as_dyn_error(&self) -> &(dyn std::error::Error + 'static) {
+                self
+            }
+        }
+
+        impl #as_dyn_error for dyn std::error::Error + 'static {
+            fn as_dyn_error(&self) -> &(dyn std::error::Error + 'static) {
+                self
+            }
+        }
+
+        impl #as_dyn_error for dyn std::error::Error + std::marker::Send + std::marker::Sync + 'static {
+            fn as_dyn_error(&self) -> &(dyn std::error::Error + 'static) {
+                self
+            }
+        }
+    }
+}
+
+/// Generate the match arm of `Error::source`.
+fn generate_source_arm(
+    variant: &Variant,
+    generics: &syn::Generics,
+    bounds: &mut Vec<syn::WherePredicate>,
+) -> proc_macro2::TokenStream {
+    let Some(source) = variant.source() else {
+        let pattern = generate_pattern(variant, |_| false);
+        return quote! { #pattern => std::option::Option::None, };
+    };
+
+    let ty = &source.field.ty;
+    if field::mentions_type_param(ty.to_token_stream(), generics) {
+        bounds.push(syn::parse_quote!(#ty: std::error::Error + 'static));
+    }
+
+    // メソッド呼び出しの自動参照外しで Box<dyn Error> の中身に対する実装が選ばれる
+    let binding = &source.binding;
+    let pattern = generate_pattern(variant, |i| std::ptr::eq(&variant.error_fields[i], source));
+    quote! {
+        #pattern => std::option::Option::Some(#binding.as_dyn_error()),
+    }
+}
+
+/// Generate `impl From<Source>` for the struct or variant with a `#[from]` field.
+fn generate_from_impl(
+    ident: &syn::Ident,
+    generics: &syn::Generics,
+    variant: &Variant,
+) -> Option<proc_macro2::TokenStream> {
+    let from = variant.from()?;
+    let path = &variant.path;
+    let ty = &from.field.ty;
+    let source = syn::Ident::new("source", Span::mixed_site());
+    let construct = match &from.field.ident {
+        Some(field_ident) => quote! { #path { #field_ident: #source } },
+        None => quote! { #path(#source) },
+    };
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    Some(quote! {
+        #[automatically_derived]
+        impl #impl_generics std::convert::From<#ty> for #ident #ty_generics #where_clause {
+            fn from(#source: #ty) -> Self {
+                #construct
+            }
+        }
+    })
+}
+
+fn expand(parsed: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let ident = &parsed.ident;
+    let formatter = field::local_ident("f");
+
+    let variants = match &parsed.data {
+        syn::Data::Struct(syn::DataStruct { fields, .. }) => {
+            vec![extract_variant(
+                quote! { Self },
+                ident,
+                &parsed.attrs,
+                fields,
+            )?]
+        }
+        syn::Data::Enum(syn::DataEnum { variants, .. }) => {
+            if let Some(attr) = parsed
+                .attrs
+                .iter()
+                .find(|attr| attr.path().is_ident("error"))
+            {
+                return Err(syn::Error::new_spanned(
+                    attr,
+                    "`#[error(\"...\")]` of an enum must be given on each variant",
+                ));
+            }
+            variants
+                .iter()
+                .map(|variant| {
+                    let variant_ident = &variant.ident;
+                    extract_variant(
+                        quote! { Self::#variant_ident },
+                        variant_ident,
+                        &variant.attrs,
+                        &variant.fields,
+                    )
+                })
+                .collect::<syn::Result<Vec<_>>>()?
+        }
+        _ => {
+            return Err(syn::Error::new(
+                ident.span(),
+                "Error can only be derived for struct or enum",
+            ))
+        }
+    };
+
+    let mut display_bounds = vec![];
+    let display_arms = variants
+        .iter()
+        .map(|variant| {
+            generate_display_arm(variant, &formatter, &parsed.generics, &mut display_bounds)
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    // Error は Debug と Display を要求するので、それらが実装される場合にだけ実装する
+    let mut error_bounds: Vec<syn::WherePredicate> = vec![
+        syn::parse_quote!(Self: std::fmt::Debug),
+        syn::parse_quote!(Self: std::fmt::Display),
+    ];
+    let source_arms: Vec<_> = variants
+        .iter()
+        .map(|variant| generate_source_arm(variant, &parsed.generics, &mut error_bounds))
+        .collect();
+    let as_dyn_error = if variants.iter().any(|variant| variant.source().is_some()) {
+        generate_as_dyn_error()
+    } else {
+        quote! {}
+    };
+
+    let from_impls = variants
+        .iter()
+        .filter_map(|variant| generate_from_impl(ident, &parsed.generics, variant));
+
+    let mut display_generics = parsed.generics.clone();
+    display_generics
+        .make_where_clause()
+        .predicates
+        .extend(display_bounds);
+    let (impl_generics, ty_generics, where_clause) = display_generics.split_for_impl();
+    let display_impl = quote! {
+        #[automatically_derived]
+        impl #impl_generics std::fmt::Display for #ident #ty_generics #where_clause {
+            fn fmt(&self, #formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                match *self {
+                    #(#display_arms)*
+                }
+            }
+        }
+    };
+
+    let mut error_generics = parsed.generics.clone();
+    error_generics
+        .make_where_clause()
+        .predicates
+        .extend(error_bounds);
+    let (impl_generics, ty_generics, where_clause) = error_generics.split_for_impl();
+    let error_impl = quote! {
+        #[automatically_derived]
+        impl #impl_generics std::error::Error for #ident #ty_generics #where_clause {
+            fn source(&self) -> std::option::Option<&(dyn std::error::Error + 'static)> {
+                #as_dyn_error
+
+                match *self {
+                    #(#source_arms)*
+                }
+            }
+        }
+    };
+
+    Ok(quote! {
+        #display_impl
+        #error_impl
+        #(#from_impls)*
+    })
+}
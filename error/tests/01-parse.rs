@@ -0,0 +1,13 @@
+// This test looks for a derive macro named Error which accepts an
+// #[error("...")] message on the struct.
+//
+// Before moving on, have your derive macro parse the macro input as a
+// syn::DeriveInput syntax tree and the message as a syn::LitStr.
+
+use derive_error::Error;
+
+#[derive(Error, Debug)]
+#[error("invalid header")]
+pub struct InvalidHeader;
+
+fn main() {}
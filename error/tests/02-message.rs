@@ -0,0 +1,58 @@
+// The message given by #[error("...")] becomes the Display impl of the error.
+// As with a format string, the placeholders name the fields of the struct, or
+// their position for tuple fields, and can use any format spec. For an enum
+// the message is given on each variant.
+//
+//     impl Display for ParseError {
+//         fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+//             match *self {
+//                 Self::Empty => f.write_fmt(format_args!("empty input")),
+//                 Self::InvalidDigit { ref digit, ref position, .. } => ...
+//             }
+//         }
+//     }
+//
+// std::error::Error is implemented as well, with `source()` returning None
+// since no field is marked as the source.
+
+use derive_error::Error;
+
+#[derive(Error, Debug)]
+pub enum ParseError {
+    #[error("empty input")]
+    Empty,
+    #[error("invalid digit {digit:?} at {position}")]
+    InvalidDigit {
+        digit: char,
+        position: usize,
+        input: String,
+    },
+    #[error("number too large: {0:#x} > {1:#x}")]
+    TooLarge(u64, u64),
+}
+
+#[derive(Error, Debug)]
+#[error("invalid header `{name}`")]
+pub struct InvalidHeader {
+    name: String,
+}
+
+fn assert_error<E: std::error::Error>(_: &E) {}
+
+fn main() {
+    let error = ParseError::InvalidDigit {
+        digit: 'x',
+        position: 3,
+        input: "12x".to_owned(),
+    };
+    assert_error(&error);
+    assert_eq!(error.to_string(), "invalid digit 'x' at 3");
+    assert_eq!(ParseError::Empty.to_string(), "empty input");
+    assert_eq!(ParseError::TooLarge(256, 255).to_string(), "number too large: 0x100 > 0xff");
+
+    let error = InvalidHeader {
+        name: "accept".to_owned(),
+    };
+    assert_eq!(error.to_string(), "invalid header `accept`");
+    assert!(std::error::Error::source(&error).is_none());
+}
@@ -0,0 +1,49 @@
+// A field marked with #[source] is returned from Error::source(), so that
+// callers can walk the chain of errors. The field can be any type which
+// implements std::error::Error, or a boxed `dyn Error`.
+
+use derive_error::Error;
+use std::error::Error as _;
+use std::io;
+
+#[derive(Error, Debug)]
+pub enum ConfigError {
+    #[error("failed to read {path}")]
+    Read {
+        path: String,
+        #[source]
+        cause: io::Error,
+    },
+    #[error("invalid config")]
+    Invalid(#[source] Box<dyn std::error::Error + Send + Sync>),
+    #[error("missing key")]
+    Missing,
+}
+
+#[derive(Error, Debug)]
+#[error("failed to start")]
+pub struct StartError {
+    #[source]
+    config: ConfigError,
+}
+
+fn main() {
+    let read = ConfigError::Read {
+        path: "app.toml".to_owned(),
+        cause: io::Error::new(io::ErrorKind::NotFound, "not found"),
+    };
+    assert_eq!(read.source().unwrap().to_string(), "not found");
+
+    let invalid = ConfigError::Invalid("expected a table".into());
+    assert_eq!(invalid.source().unwrap().to_string(), "expected a table");
+    assert!(ConfigError::Missing.source().is_none());
+
+    let error = StartError { config: read };
+    let mut chain = vec![];
+    let mut current: Option<&dyn std::error::Error> = Some(&error);
+    while let Some(error) = current {
+        chain.push(error.to_string());
+        current = error.source();
+    }
+    assert_eq!(chain, ["failed to start", "failed to read app.toml", "not found"]);
+}
@@ -0,0 +1,58 @@
+// A field marked with #[from] is the source of the error as well, and a From
+// impl is generated so that `?` converts the underlying error:
+//
+//     impl From<io::Error> for AppError {
+//         fn from(source: io::Error) -> Self {
+//             Self::Io(source)
+//         }
+//     }
+//
+// Since the other fields could not be filled in, #[from] requires the struct
+// or variant to have exactly one field.
+
+use derive_error::Error;
+use std::error::Error as _;
+use std::io;
+use std::num::ParseIntError;
+
+#[derive(Error, Debug)]
+pub enum AppError {
+    #[error("I/O error")]
+    Io(#[from] io::Error),
+    #[error("invalid number")]
+    Parse {
+        #[from]
+        source: ParseIntError,
+    },
+}
+
+#[derive(Error, Debug)]
+#[error("wrapped: {0}")]
+pub struct Wrapped(#[from] AppError);
+
+fn parse(input: &str) -> Result<u32, AppError> {
+    Ok(input.parse::<u32>()?)
+}
+
+fn read() -> Result<(), AppError> {
+    Err(io::Error::new(io::ErrorKind::Other, "disk full"))?
+}
+
+fn wrapped() -> Result<u32, Wrapped> {
+    Ok(parse("nope")?)
+}
+
+fn main() {
+    assert_eq!(parse("42").unwrap(), 42);
+
+    let error = parse("x").unwrap_err();
+    assert!(matches!(error, AppError::Parse { .. }));
+    assert_eq!(error.source().unwrap().to_string(), "invalid digit found in string");
+
+    let error = read().unwrap_err();
+    assert_eq!(error.to_string(), "I/O error");
+    assert_eq!(error.source().unwrap().to_string(), "disk full");
+
+    let error = wrapped().unwrap_err();
+    assert_eq!(error.to_string(), "wrapped: invalid number");
+}
@@ -0,0 +1,44 @@
+// Fields whose type refers to a type parameter are bounded by the traits they
+// are used with: the traits of their placeholders for Display, and
+// `Error + 'static` for the source. The Error impl also requires the error to
+// implement Debug and Display.
+
+use derive_error::Error;
+use std::error::Error as _;
+use std::fmt;
+
+#[derive(Error, Debug)]
+pub enum RetryError<E, T> {
+    #[error("gave up after {attempts} attempts")]
+    GaveUp {
+        attempts: u32,
+        #[source]
+        last: E,
+    },
+    #[error("unexpected value {0:?}")]
+    Unexpected(T),
+}
+
+#[derive(Debug)]
+pub struct Timeout;
+
+impl fmt::Display for Timeout {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("timed out")
+    }
+}
+
+impl std::error::Error for Timeout {}
+
+fn main() {
+    let error = RetryError::<_, ()>::GaveUp {
+        attempts: 3,
+        last: Timeout,
+    };
+    assert_eq!(error.to_string(), "gave up after 3 attempts");
+    assert_eq!(error.source().unwrap().to_string(), "timed out");
+
+    let error = RetryError::<Timeout, _>::Unexpected(vec![1]);
+    assert_eq!(error.to_string(), "unexpected value [1]");
+    assert!(error.source().is_none());
+}
@@ -0,0 +1,62 @@
+// Misuse of the attributes is reported at the attribute that causes it.
+
+use derive_error::Error;
+use std::io;
+
+#[derive(Error, Debug)]
+pub struct MissingMessage {
+    path: String,
+}
+
+#[derive(Error, Debug)]
+pub enum MissingVariantMessage {
+    #[error("first")]
+    First,
+    Second,
+}
+
+#[derive(Error, Debug)]
+pub enum FromWithOtherFields {
+    #[error("failed to read {path}")]
+    Read {
+        path: String,
+        #[from]
+        source: io::Error,
+    },
+}
+
+#[derive(Error, Debug)]
+#[error("two sources")]
+pub struct TwoSources {
+    #[source]
+    first: io::Error,
+    #[source]
+    second: io::Error,
+}
+
+#[derive(Error, Debug)]
+#[error("duplicate")]
+pub struct DuplicateSource(
+    #[source]
+    #[source]
+    io::Error,
+);
+
+#[derive(Error, Debug)]
+#[error("arguments")]
+pub struct SourceWithArguments(#[source(io)] io::Error);
+
+#[derive(Error, Debug)]
+#[error("unknown {code}")]
+pub struct UnknownField {
+    status: u16,
+}
+
+#[derive(Error, Debug)]
+#[error("error")]
+pub enum OnEnum {
+    #[error("variant")]
+    Variant,
+}
+
+fn main() {}
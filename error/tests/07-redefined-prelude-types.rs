@@ -0,0 +1,36 @@
+// The generated code refers to std items by their absolute path, so that it
+// keeps working when the caller redefines prelude names such as Option or
+// Result, e.g. with a crate-specific Result alias.
+
+use derive_error::Error;
+use std::io;
+
+type Option = ();
+type Some = ();
+type None = ();
+type Result = ();
+type Box = ();
+type Send = ();
+type Sync = ();
+
+#[derive(Error, Debug)]
+pub enum AppError {
+    #[error("I/O error")]
+    Io(#[from] io::Error),
+    #[error("config error: {0}")]
+    Config(
+        #[source]
+        std::boxed::Box<dyn std::error::Error + std::marker::Send + std::marker::Sync>,
+    ),
+    #[error("unknown error")]
+    Unknown,
+}
+
+#[derive(Error, Debug)]
+#[error("failed to start")]
+pub struct StartError {
+    #[source]
+    cause: AppError,
+}
+
+fn main() {}
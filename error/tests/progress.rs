@@ -0,0 +1,11 @@
+#[test]
+fn tests() {
+    let t = trybuild::TestCases::new();
+    t.pass("tests/01-parse.rs");
+    t.pass("tests/02-message.rs");
+    t.pass("tests/03-source.rs");
+    t.pass("tests/04-from.rs");
+    t.pass("tests/05-generics.rs");
+    t.compile_fail("tests/06-misuse.rs");
+    t.pass("tests/07-redefined-prelude-types.rs");
+}
@@ -1,5 +1,6 @@
 use derive_builder::Builder;
 
+#[allow(dead_code)]
 #[derive(Builder)]
 struct Command {
     executable: String,